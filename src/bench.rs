@@ -0,0 +1,43 @@
+use crate::game::{multi_strategy::MultiStrategy, Game};
+use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+use crate::strategies::{mcts, random, Strategy};
+use std::time::{Duration, Instant};
+
+/// Seed of the search in `mcts_nps`. Workloads are seeded so repeated runs do the same work and
+/// only the timings vary.
+const MCTS_SEED: u64 = 0;
+
+/// Time to play `games` games of chopsticks between seeded `Random` strategies, where game `g`
+/// seeds its players with `seed + g`
+pub fn random_playout_count(games: usize, seed: u64) -> Duration {
+    let start = Instant::now();
+    for g in 0..games as u64 {
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+            Box::new(random::Random::seeded(seed.wrapping_add(g))),
+            Box::new(random::Random::seeded(seed.wrapping_add(g).wrapping_add(1))),
+        ];
+        MultiStrategy::new(Chopsticks.get_initial_state(), strategies).get_rankings();
+    }
+    start.elapsed()
+}
+
+/// Iterations per second of a seeded `Mcts` choosing a first action with `iterations`
+/// iterations
+pub fn mcts_nps(iterations: usize) -> f64 {
+    let state = Chopsticks.get_initial_state();
+    let mut mcts = mcts::Mcts::seeded(iterations, std::f64::consts::SQRT_2, MCTS_SEED);
+    let start = Instant::now();
+    mcts.get_action(&state);
+    iterations as f64 / start.elapsed().as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoke() {
+        assert!(random_playout_count(10, 0) > Duration::ZERO);
+        assert!(mcts_nps(50) > 0.0);
+    }
+}
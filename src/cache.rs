@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+/// Number of entries a `TranspositionTable` holds unless configured otherwise
+pub const DEFAULT_CAPACITY: usize = 1 << 16;
+
+/// Memoized values keyed by a `u32` state serial of the caller's choosing, such as
+/// `serialize_state` or `canonical_serial`. Once `capacity` entries are stored new values are
+/// still computed but no longer stored.
+#[derive(Clone, Debug)]
+pub struct TranspositionTable<V> {
+    entries: HashMap<u32, V>,
+    capacity: usize,
+    hits: usize,
+    misses: usize,
+}
+
+impl<V: Clone> TranspositionTable<V> {
+    pub fn new(capacity: usize) -> TranspositionTable<V> {
+        TranspositionTable {
+            entries: HashMap::new(),
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Stored value for `serial` or the value of `f` which is stored while under capacity
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, serial: u32, f: F) -> V {
+        if let Some(value) = self.entries.get(&serial) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = f();
+        if self.entries.len() < self.capacity {
+            self.entries.insert(serial, value.clone());
+        }
+        value
+    }
+
+    /// Stores `value` for `serial` while under capacity
+    pub fn insert(&mut self, serial: u32, value: V) {
+        if self.entries.len() < self.capacity || self.entries.contains_key(&serial) {
+            self.entries.insert(serial, value);
+        }
+    }
+
+    /// Stored value for `serial` without affecting the stats
+    pub fn get(&self, serial: u32) -> Option<&V> {
+        self.entries.get(&serial)
+    }
+
+    /// Removes every entry and resets the stats
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Number of stored entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Lookups answered by a stored value
+    pub fn get_hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Lookups that computed a value
+    pub fn get_misses(&self) -> usize {
+        self.misses
+    }
+}
+
+impl<V: Clone> Default for TranspositionTable<V> {
+    fn default() -> TranspositionTable<V> {
+        TranspositionTable::new(DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_and_misses() {
+        let mut table = TranspositionTable::new(1);
+        assert_eq!(table.get_or_insert_with(0, || 1), 1);
+        assert_eq!(table.get_or_insert_with(0, || 2), 1);
+        assert_eq!(table.get_or_insert_with(1, || 3), 3);
+        assert_eq!(table.get_or_insert_with(1, || 4), 4);
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get_hits(), 1);
+        assert_eq!(table.get_misses(), 3);
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(table.get_hits(), 0);
+    }
+}
@@ -1,11 +1,68 @@
 use crate::{state, state_space};
+use std::collections::HashMap;
 
 // A trait may be over-engineering the problem at hand.
 
 pub mod multi_strategy;
+pub mod review;
 pub mod single_strategy;
 
-/// Encapsulates gameplay within a certain statespace amoung players.
+/// Default number of times a `State` may occur before the game is a draw
+pub const MAX_REPETITIONS: usize = 3;
+
+/// Default number of plies after which the game is a draw
+pub const MAX_PLIES: usize = 1000;
+
+/// Occurrences of each `State` of a game keyed by `serialize_state` for the repetition draw of
+/// `Game::get_rankings`
+#[derive(Clone, Debug, Default)]
+pub struct Repetitions {
+    counts: HashMap<u32, usize>,
+}
+
+impl Repetitions {
+    /// Counts another occurrence of `state` and returns how many times it has occurred
+    pub fn record<const N: usize, T: state_space::StateSpace<N>>(
+        &mut self,
+        state: &state::State<N, T>,
+    ) -> usize {
+        let count = self.counts.entry(T::serialize_state(state)).or_insert(0);
+        *count += 1;
+        *count
+    }
+}
+
+/// A `Game` behind dynamic dispatch.
+pub type BoxedGame<const N: usize, T> = Box<dyn Game<N, T>>;
+
+/// Applies `history` to `initial` in order. Errors with the index and error of the first
+/// invalid action.
+pub fn replay<const N: usize, T: state_space::StateSpace<N>>(
+    initial: state::State<N, T>,
+    history: &[state::action::Action<N, T>],
+) -> Result<state::State<N, T>, (usize, state::action::ActionError)> {
+    let mut state = initial;
+    for (k, action) in history.iter().enumerate() {
+        state.play_action(action).map_err(|error| (k, error))?;
+    }
+    Ok(state)
+}
+
+/// Actions of a game written by `Game::to_notation`, one per line or separated by `;`. Errors
+/// with the index and error of the first action that does not parse.
+pub fn parse_game<const N: usize, T: state_space::StateSpace<N>>(
+    notation: &str,
+) -> Result<Vec<state::action::Action<N, T>>, (usize, state::action::ParseActionError)> {
+    notation
+        .split(['\n', ';'])
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(k, line)| line.parse().map_err(|error| (k, error)))
+        .collect()
+}
+
+/// Encapsulates gameplay within a certain statespace amoung players. Methods should stay object
+/// safe, returning `Vec`s rather than `impl Iterator`s, so that `BoxedGame` keeps working.
 pub trait Game<const N: usize, T: state_space::StateSpace<N>> {
     fn get_action(&mut self) -> Option<state::action::Action<N, T>>;
 
@@ -14,23 +71,473 @@ pub trait Game<const N: usize, T: state_space::StateSpace<N>> {
         action: &state::action::Action<N, T>,
     ) -> Result<(), state::action::ActionError>;
 
+    /// Plays `action` then calls `observer` with the resulting state and `action`. The
+    /// observer is not called if `action` is invalid.
+    fn play_action_with(
+        &mut self,
+        action: &state::action::Action<N, T>,
+        observer: &mut dyn FnMut(&state::State<N, T>, &state::action::Action<N, T>),
+    ) -> Result<(), state::action::ActionError> {
+        self.play_action(action)?;
+        observer(self.get_state(), action);
+        Ok(())
+    }
+
     fn get_state(&self) -> &state::State<N, T>;
 
-    /// The rank in `1..=N` of each player or `N` if they were already dead
+    /// Actions played so far in order
+    fn get_history(&self) -> &[state::action::Action<N, T>];
+
+    /// Number of plies played so far
+    fn get_ply_count(&self) -> usize {
+        self.get_history().len()
+    }
+
+    /// `get_history` with one action per line which `parse_game` reads back
+    fn to_notation(&self) -> String {
+        let actions: Vec<_> = self.get_history().iter().map(|a| a.to_notation()).collect();
+        actions.join("\n")
+    }
+
+    /// One numbered line per action of `get_history` with the abbreviation after it, such as
+    /// `1. P0 h0 -> P1 h1  => 1112`. The initial state is recovered by undoing the history.
+    fn transcript(&self) -> Vec<String> {
+        let history = self.get_history();
+        let mut state = self.get_state().clone();
+        for action in history.iter().rev() {
+            state.undo_action(action).expect("valid history");
+        }
+        history
+            .iter()
+            .enumerate()
+            .map(|(k, action)| {
+                state.play_action(action).expect("valid history");
+                format!("{}. {action}  => {}", k + 1, state.get_abbreviation())
+            })
+            .collect()
+    }
+
+    /// Number of times a `State` may occur before the game is a draw
+    fn get_max_repetitions(&self) -> usize;
+
+    /// Number of plies `get_rankings` plays before the game is a draw
+    fn get_max_plies(&self) -> usize {
+        MAX_PLIES
+    }
+
+    /// The rank in `1..=N` of each player or `N` if they were already dead. The game is a draw
+    /// among the remaining players once a `State` occurs `get_max_repetitions` times or after
+    /// `get_max_plies` plies. In misère the order of eliminations is reversed and players left
+    /// standing are ranked `N`.
     fn get_rankings(&mut self) -> [usize; N] {
         let mut ranks = [N; N];
+        let mut alive = self
+            .get_state()
+            .get_players()
+            .each_ref()
+            .map(|p| !p.is_eliminated());
+        let mut n_alive = alive.iter().filter(|&&is_alive| is_alive).count();
+        let mut repetitions = Repetitions::default();
+        repetitions.record(self.get_state());
+        let mut plies = 0;
         while let state::status::Status::Turn { i: _ } = self.get_state().get_status() {
-            if self.get_state().is_loop_state() {
+            if plies >= self.get_max_plies() {
                 break;
             }
+            plies += 1;
             let action = self.get_action().expect("ongoing game");
             self.play_action(&action).expect("valid action");
-            let player_ids: Vec<_> = self.get_state().iter_player_indexes().collect();
-            let n_players = player_ids.len();
-            for id in player_ids {
-                ranks[id] = n_players;
+            // Players eliminated by the same ply share the rank of the players alive before it
+            let previously_alive = n_alive;
+            for (id, player) in self.get_state().get_players().iter().enumerate() {
+                if alive[id] && player.is_eliminated() {
+                    alive[id] = false;
+                    ranks[id] = previously_alive;
+                    n_alive -= 1;
+                }
+            }
+            if repetitions.record(self.get_state()) >= self.get_max_repetitions() {
+                break;
+            }
+        }
+        for (rank, is_alive) in ranks.iter_mut().zip(alive) {
+            if is_alive {
+                *rank = if T::MISERE { N } else { n_alive };
+            } else if T::MISERE {
+                *rank = N + 1 - *rank;
             }
         }
         ranks
     }
+
+    /// Distinct placements in `1..=N` from `get_rankings`, so players eliminated later are
+    /// placed higher. Players that share a rank, such as those left in a drawn game, are placed
+    /// in an arbitrary order so every game has a single first place.
+    fn final_placements(&mut self) -> [usize; N] {
+        let ranks = self.get_rankings();
+        let mut order: [usize; N] = std::array::from_fn(|id| id);
+        order.sort_by_key(|&id| ranks[id]);
+        let mut placements = [N; N];
+        for (k, id) in order.into_iter().enumerate() {
+            placements[id] = k + 1;
+        }
+        placements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{
+        chopsticks::Chopsticks, misere_chopsticks::MisereChopsticks, three_handed::ThreeHanded,
+        StateSpace,
+    };
+    use crate::strategies::{random, scripted, Strategy};
+
+    /// Kills a hand when possible
+    struct Aggressive;
+
+    impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for Aggressive {
+        fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+            state
+                .iter_attack_actions()
+                .find(|action| match *action {
+                    state::action::Action::Attack { i, j, a, b } => {
+                        state.get_players()[i].hands[a] + state.get_players()[j].hands[b]
+                            == T::ROLLOVER
+                    }
+                    _ => false,
+                })
+                .or_else(|| state.iter_actions().next())
+                .expect("multiple actions")
+        }
+    }
+
+    /// Rankings of two `Aggressive` players starting from "1410"
+    fn aggressive_rankings<T: StateSpace<2> + std::fmt::Debug>() -> [usize; 2] {
+        let state = state::State::<2, T>::from_abbreviation("1410").expect("valid");
+        let strategies: [Box<dyn Strategy<2, T>>; 2] = [Box::new(Aggressive), Box::new(Aggressive)];
+        multi_strategy::MultiStrategy::new(state, strategies).get_rankings()
+    }
+
+    /// Always splits the same way given the same hands
+    struct FirstSplit;
+
+    impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for FirstSplit {
+        fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+            state.iter_split_actions().next().expect("split action")
+        }
+    }
+
+    #[test]
+    fn repetition_draw() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("2323").expect("valid");
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] =
+            [Box::new(FirstSplit), Box::new(FirstSplit)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        assert_eq!(game.get_rankings(), [2, 2]);
+        assert_eq!(game.history.len(), 4 * (MAX_REPETITIONS - 1));
+    }
+
+    #[test]
+    fn repetitions() {
+        let mut repetitions = Repetitions::default();
+        let mut state = Chopsticks.get_initial_state();
+        assert_eq!(repetitions.record(&state), 1);
+        assert!(state.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(repetitions.record(&state), 1);
+        assert_eq!(repetitions.record(&state), 2);
+        // The turn is part of the serial
+        let state = state.with_starting_player(0).expect("valid");
+        assert_eq!(repetitions.record(&state), 1);
+    }
+
+    #[test]
+    fn max_repetitions() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("2323").expect("valid");
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] =
+            [Box::new(FirstSplit), Box::new(FirstSplit)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        game.max_repetitions = 2;
+        assert_eq!(game.get_rankings(), [2, 2]);
+        assert_eq!(game.history.len(), 4);
+    }
+
+    #[test]
+    fn max_plies() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("2323").expect("valid");
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] =
+            [Box::new(FirstSplit), Box::new(FirstSplit)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        game.max_repetitions = usize::MAX;
+        game.max_plies = 10;
+        assert_eq!(game.get_rankings(), [2, 2]);
+        assert_eq!(game.history.len(), 10);
+    }
+
+    /// Always passes
+    struct AlwaysPass;
+
+    impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for AlwaysPass {
+        fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+            state.get_pass_action().expect("pass action")
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Passing;
+
+    impl StateSpace<2> for Passing {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const ALLOW_PASS: bool = true;
+    }
+
+    #[test]
+    fn pass_repetition_draw() {
+        let state = Passing.get_initial_state();
+        let strategies: [Box<dyn Strategy<2, Passing>>; 2] =
+            [Box::new(AlwaysPass), Box::new(AlwaysPass)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        assert_eq!(game.get_rankings(), [2, 2]);
+        assert_eq!(game.history.len(), 2 * (MAX_REPETITIONS - 1));
+    }
+
+    #[test]
+    fn three_handed_repetition_draw() {
+        let state = state::State::<2, ThreeHanded>::from_abbreviation("122122").expect("valid");
+        let strategies: [Box<dyn Strategy<2, ThreeHanded>>; 2] =
+            [Box::new(FirstSplit), Box::new(FirstSplit)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        assert_eq!(game.get_rankings(), [2, 2]);
+        assert_eq!(game.history.len(), 4 * (MAX_REPETITIONS - 1));
+    }
+
+    #[test]
+    fn rankings() {
+        assert_eq!(aggressive_rankings::<Chopsticks>(), [1, 2]);
+    }
+
+    #[test]
+    fn decisive_rankings() {
+        // Each player plays their half of `short_game_history` which player 0 wins
+        let history = short_game_history();
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+            Box::new(scripted::Scripted::new(
+                history.iter().step_by(2).copied().collect(),
+            )),
+            Box::new(scripted::Scripted::new(
+                history.iter().skip(1).step_by(2).copied().collect(),
+            )),
+        ];
+        let mut game =
+            multi_strategy::MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+        assert_eq!(game.get_rankings(), [1, 2]);
+        assert_eq!(game.get_ply_count(), 5);
+    }
+
+    #[test]
+    fn misere_rankings() {
+        assert_eq!(aggressive_rankings::<MisereChopsticks>(), [2, 1]);
+    }
+
+    #[test]
+    fn final_placements() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] =
+            [Box::new(Aggressive), Box::new(Aggressive)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        assert_eq!(game.final_placements(), [1, 2]);
+        // Draws are placed in some order
+        let state = state::State::<2, Chopsticks>::from_abbreviation("2323").expect("valid");
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] =
+            [Box::new(FirstSplit), Box::new(FirstSplit)];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        let mut placements = game.final_placements();
+        placements.sort_unstable();
+        assert_eq!(placements, [1, 2]);
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct ThreePlayers;
+
+    impl StateSpace<3> for ThreePlayers {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn elimination_placements() {
+        // Player 1 is eliminated on the first ply and player 2 on the third
+        let state = state::State::<3, ThreePlayers>::from_abbreviation("110404").expect("valid");
+        let attack = |i, j, a, b| state::action::Action::Attack { i, j, a, b };
+        let strategies: [Box<dyn Strategy<3, ThreePlayers>>; 3] = [
+            Box::new(scripted::Scripted::new(vec![
+                attack(0, 1, 0, 1),
+                attack(0, 2, 1, 1),
+            ])),
+            Box::new(scripted::Scripted::new(Vec::new())),
+            Box::new(scripted::Scripted::new(vec![attack(2, 0, 1, 0)])),
+        ];
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        assert_eq!(game.final_placements(), [1, 3, 2]);
+        assert_eq!(game.history.len(), 3);
+    }
+
+    /// The actions of `state::tests::short_game`
+    fn short_game_history() -> Vec<state::action::Action<2, Chopsticks>> {
+        [
+            (0, 1, 0, 1),
+            (1, 0, 1, 1),
+            (0, 1, 1, 1),
+            (1, 0, 0, 1),
+            (0, 1, 1, 0),
+        ]
+        .into_iter()
+        .map(|(i, j, a, b)| state::action::Action::Attack { i, j, a, b })
+        .collect()
+    }
+
+    #[test]
+    fn replay_short_game() {
+        let state = replay(Chopsticks.get_initial_state(), &short_game_history()).expect("valid");
+        assert_eq!(state.get_abbreviation(), "1400");
+        assert!(matches!(
+            state.get_status(),
+            state::status::Status::Over { i: 0 }
+        ));
+    }
+
+    #[test]
+    fn replay_invalid_action() {
+        let mut history = short_game_history();
+        history.swap(1, 2);
+        assert!(matches!(
+            replay(Chopsticks.get_initial_state(), &history),
+            Err((1, state::action::ActionError::WrongTurn))
+        ));
+    }
+
+    #[test]
+    fn history() {
+        let history = short_game_history();
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] =
+            [Box::new(FirstSplit), Box::new(FirstSplit)];
+        let mut game: BoxedGame<2, Chopsticks> = Box::new(multi_strategy::MultiStrategy::new(
+            Chopsticks.get_initial_state(),
+            strategies,
+        ));
+        assert_eq!(game.get_ply_count(), 0);
+        for action in history.iter().take(3) {
+            assert!(game.play_action(action).is_ok());
+        }
+        // Invalid actions are not recorded
+        assert!(game.play_action(&history[0]).is_err());
+        assert_eq!(game.get_ply_count(), 3);
+        assert_eq!(game.get_history(), &history[..3]);
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+            Box::new(random::Random::default()),
+            Box::new(random::Random::default()),
+        ];
+        let mut game =
+            multi_strategy::MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+        game.get_rankings();
+        let history = parse_game(&game.to_notation()).expect("valid notation");
+        assert_eq!(history, game.get_history());
+        let state = replay(Chopsticks.get_initial_state(), &history).expect("valid history");
+        assert_eq!(&state, game.get_state());
+    }
+
+    #[test]
+    fn parse_game_separators() {
+        let history: Vec<state::action::Action<2, Chopsticks>> =
+            parse_game("a 0 1 0 1; a 1 0 1 1\n\na 0 1 1 1\n").expect("valid notation");
+        assert_eq!(history, short_game_history()[..3]);
+        assert!(matches!(
+            parse_game::<2, Chopsticks>("a 0 1 0 1;x 1 0 1 1"),
+            Err((1, state::action::ParseActionError::UnknownKind))
+        ));
+    }
+
+    #[test]
+    fn starting_player_rankings() {
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+            Box::new(random::Random::default()),
+            Box::new(random::Random::default()),
+        ];
+        let state = Chopsticks
+            .get_initial_state()
+            .with_starting_player(1)
+            .expect("valid");
+        let mut game = multi_strategy::MultiStrategy::new(state, strategies);
+        game.get_rankings();
+        assert_eq!(game.get_history()[0].get_i(), 1);
+    }
+
+    #[test]
+    fn transcript() {
+        let mut strategy = FirstSplit;
+        let mut game =
+            single_strategy::SingleStrategy::new(Chopsticks.get_initial_state(), &mut strategy);
+        for action in short_game_history() {
+            assert!(game.play_action(&action).is_ok());
+        }
+        assert_eq!(
+            game.transcript(),
+            [
+                "1. P0 h0 -> P1 h1  => 1112",
+                "2. P1 h1 -> P0 h1  => 1312",
+                "3. P0 h1 -> P1 h1  => 1310",
+                "4. P1 h0 -> P0 h1  => 1410",
+                "5. P0 h1 -> P1 h0  => 1400",
+            ]
+        );
+    }
+
+    #[test]
+    fn observer() {
+        let history = short_game_history();
+        let mut strategy = FirstSplit;
+        let mut game =
+            single_strategy::SingleStrategy::new(Chopsticks.get_initial_state(), &mut strategy);
+        let mut observed = Vec::new();
+        for action in history.iter() {
+            let result = game.play_action_with(action, &mut |state, action| {
+                observed.push((state.get_abbreviation(), *action));
+            });
+            assert!(result.is_ok());
+        }
+        assert!(game
+            .play_action_with(&history[0], &mut |_, _| panic!("invalid action observed"))
+            .is_err());
+        assert_eq!(observed.len(), history.len());
+        assert!(observed
+            .iter()
+            .zip(history.iter())
+            .all(|((_, observed), action)| observed == action));
+        assert_eq!(observed[4].0, "1400");
+    }
+
+    #[test]
+    fn boxed_game() {
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+            Box::new(random::Random::default()),
+            Box::new(random::Random::default()),
+        ];
+        let mut game: BoxedGame<2, Chopsticks> = Box::new(multi_strategy::MultiStrategy::new(
+            Chopsticks.get_initial_state(),
+            strategies,
+        ));
+        let action = game.get_action().expect("ongoing game");
+        assert!(game.play_action(&action).is_ok());
+        assert_eq!(game.get_state().get_i(), 1);
+        let ranks = game.get_rankings();
+        assert!(ranks.iter().all(|rank| (1..=2).contains(rank)));
+    }
 }
@@ -1,11 +1,13 @@
 pub use crate::game::Game;
 use crate::{state, state_space, strategies};
 
-/// Each player's actions is determined by its own controller.
+/// Each player's actions are determined by its own strategy.
 pub struct MultiStrategy<const N: usize, T: state_space::StateSpace<N>> {
     pub strategies: [Box<dyn strategies::Strategy<N, T>>; N], // could be Rc RefCell for player re-use
     pub state: state::State<N, T>,
     pub history: Vec<state::action::Action<N, T>>,
+    pub max_repetitions: usize,
+    pub max_plies: usize,
 }
 
 impl<const N: usize, T: state_space::StateSpace<N>> MultiStrategy<N, T> {
@@ -17,6 +19,8 @@ impl<const N: usize, T: state_space::StateSpace<N>> MultiStrategy<N, T> {
             strategies,
             state,
             history: Vec::new(),
+            max_repetitions: crate::game::MAX_REPETITIONS,
+            max_plies: crate::game::MAX_PLIES,
         }
     }
 }
@@ -33,11 +37,24 @@ impl<const N: usize, T: state_space::StateSpace<N>> Game<N, T> for MultiStrategy
         &mut self,
         action: &state::action::Action<N, T>,
     ) -> Result<(), state::action::ActionError> {
+        self.state.play_action(action)?;
         self.history.push(*action);
-        self.state.play_action(action)
+        Ok(())
     }
 
     fn get_state(&self) -> &state::State<N, T> {
         &self.state
     }
+
+    fn get_history(&self) -> &[state::action::Action<N, T>] {
+        &self.history
+    }
+
+    fn get_max_repetitions(&self) -> usize {
+        self.max_repetitions
+    }
+
+    fn get_max_plies(&self) -> usize {
+        self.max_plies
+    }
 }
@@ -0,0 +1,122 @@
+use crate::{state, state_space};
+
+/// A cursor over a recorded game that plays or undoes one action at a time
+pub struct GameReview<const N: usize, T: state_space::StateSpace<N>> {
+    state: state::State<N, T>,
+    actions: Vec<state::action::Action<N, T>>,
+    ply: usize,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> GameReview<N, T> {
+    /// Starts at `initial` once every action is checked by `replay`
+    pub fn new(
+        initial: state::State<N, T>,
+        actions: Vec<state::action::Action<N, T>>,
+    ) -> Result<GameReview<N, T>, (usize, state::action::ActionError)> {
+        crate::game::replay(initial.clone(), &actions)?;
+        Ok(GameReview {
+            state: initial,
+            actions,
+            ply: 0,
+        })
+    }
+
+    /// The `State` after the first `get_ply` actions
+    pub fn current_state(&self) -> &state::State<N, T> {
+        &self.state
+    }
+
+    /// Number of actions played from the initial state
+    pub fn get_ply(&self) -> usize {
+        self.ply
+    }
+
+    pub fn get_actions(&self) -> &[state::action::Action<N, T>] {
+        &self.actions
+    }
+
+    /// Plays the next action or returns false at the end of the game
+    pub fn step_forward(&mut self) -> bool {
+        let Some(action) = self.actions.get(self.ply) else {
+            return false;
+        };
+        self.state.play_action(action).expect("valid action");
+        self.ply += 1;
+        true
+    }
+
+    /// Undoes the previous action or returns false at the initial state
+    pub fn step_back(&mut self) -> bool {
+        let Some(ply) = self.ply.checked_sub(1) else {
+            return false;
+        };
+        self.state
+            .undo_action(&self.actions[ply])
+            .expect("valid action");
+        self.ply = ply;
+        true
+    }
+
+    /// Steps to `ply` or returns false, without moving, if it is past the end of the game
+    pub fn goto(&mut self, ply: usize) -> bool {
+        if ply > self.actions.len() {
+            return false;
+        }
+        while self.ply < ply {
+            self.step_forward();
+        }
+        while self.ply > ply {
+            self.step_back();
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn navigate_short_game() {
+        let actions = [
+            (0, 1, 0, 1),
+            (1, 0, 1, 1),
+            (0, 1, 1, 1),
+            (1, 0, 0, 1),
+            (0, 1, 1, 0),
+        ]
+        .into_iter()
+        .map(|(i, j, a, b)| state::action::Action::Attack { i, j, a, b })
+        .collect();
+        let mut review = GameReview::new(Chopsticks.get_initial_state(), actions).expect("valid");
+        assert!(!review.step_back());
+        let mut abbreviations = Vec::new();
+        while review.step_forward() {
+            abbreviations.push(review.current_state().get_abbreviation());
+        }
+        assert_eq!(abbreviations, ["1112", "1312", "1310", "1410", "1400"]);
+        assert_eq!(review.get_ply(), 5);
+        assert!(review.current_state().is_terminal());
+        assert!(!review.goto(6));
+        assert!(review.goto(2));
+        assert_eq!(review.current_state().get_abbreviation(), "1312");
+        while review.step_back() {}
+        assert_eq!(review.get_ply(), 0);
+        assert_eq!(*review.current_state(), Chopsticks.get_initial_state());
+    }
+
+    #[test]
+    fn invalid_actions() {
+        let actions = vec![state::action::Action::Attack {
+            i: 1,
+            j: 0,
+            a: 0,
+            b: 0,
+        }];
+        assert!(matches!(
+            GameReview::new(Chopsticks.get_initial_state(), actions),
+            Err((0, state::action::ActionError::WrongTurn))
+        ));
+    }
+}
@@ -1,11 +1,13 @@
 pub use crate::game::Game;
 use crate::{state, state_space, strategies};
 
-// One controller determines all moves for a game.
+/// One strategy determines all moves for a game.
 pub struct SingleStrategy<'a, const N: usize, T: state_space::StateSpace<N>> {
     pub strategy: &'a mut dyn strategies::Strategy<N, T>,
     pub state: state::State<N, T>,
     pub history: Vec<state::action::Action<N, T>>,
+    pub max_repetitions: usize,
+    pub max_plies: usize,
 }
 
 impl<'a, const N: usize, T: state_space::StateSpace<N>> SingleStrategy<'a, N, T> {
@@ -17,6 +19,8 @@ impl<'a, const N: usize, T: state_space::StateSpace<N>> SingleStrategy<'a, N, T>
             strategy,
             state,
             history: Vec::new(),
+            max_repetitions: crate::game::MAX_REPETITIONS,
+            max_plies: crate::game::MAX_PLIES,
         }
     }
 }
@@ -33,11 +37,24 @@ impl<'a, const N: usize, T: state_space::StateSpace<N>> Game<N, T> for SingleStr
         &mut self,
         action: &state::action::Action<N, T>,
     ) -> Result<(), state::action::ActionError> {
+        self.state.play_action(action)?;
         self.history.push(*action);
-        self.state.play_action(action)
+        Ok(())
     }
 
     fn get_state(&self) -> &state::State<N, T> {
         &self.state
     }
+
+    fn get_history(&self) -> &[state::action::Action<N, T>] {
+        &self.history
+    }
+
+    fn get_max_repetitions(&self) -> usize {
+        self.max_repetitions
+    }
+
+    fn get_max_plies(&self) -> usize {
+        self.max_plies
+    }
 }
@@ -1,4 +1,14 @@
+#[cfg(any(test, feature = "bench"))]
+pub mod bench;
+pub mod cache;
 pub mod game;
+pub mod perft;
+pub mod presets;
+pub mod simulate;
+pub mod solver;
 pub mod state;
 pub mod state_space;
 pub mod strategies;
+pub mod tournament;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,5 +1,4 @@
 use ::chopsticks::game::*;
-use ::chopsticks::state::action::Action;
 use ::chopsticks::state::status::Status;
 use ::chopsticks::state_space::*;
 use ::chopsticks::strategies::*;
@@ -11,29 +10,23 @@ fn main() {
     let players: [Box<dyn Strategy<2, chopsticks::Chopsticks>>; 2] = [player_1, player_2];
     let mut game =
         multi_strategy::MultiStrategy::new(chopsticks::Chopsticks.get_initial_state(), players);
-    while let Status::Turn { .. } = game.state.get_status() {
-        if game.state.is_loop_state() {
-            break;
-        }
-        println!("{}", game.state.get_abbreviation());
+    // The same draw by repetition or by length as `Game::get_rankings`
+    let mut repetitions = Repetitions::default();
+    let mut is_draw = repetitions.record(&game.state) >= game.get_max_repetitions();
+    while !is_draw && !game.state.is_terminal() {
+        println!("{}", game.state);
         let action = game.get_action().unwrap();
-        match action {
-            Action::Attack { i, j, a, b } => {
-                println!("Player {i} uses hand {a} to attack hand {b} of player index {j}")
-            }
-            Action::Split { i, hands_0: [a, b], hands_1: [c, d] } => {
-                println!("Player {i} split {a}{b} into {c}{d}",)
-            }
-            _ => panic!("expect not phantom"),
-        }
-        if game.play_action(&action).is_err() {
-            // Human player tried something invalid or there is a bug in a controller
+        let result = game.play_action_with(&action, &mut |_, action| println!("{action}"));
+        if result.is_err() {
+            // Human player tried something invalid or there is a bug in a strategy
             println!("Action was not valid. Try again.");
             continue;
         }
+        is_draw = repetitions.record(&game.state) >= game.get_max_repetitions()
+            || game.get_ply_count() >= game.get_max_plies();
     }
     match game.state.get_status() {
-        Status::Over { i } => println!("Player {i}, you won!"),
+        Status::Over { .. } => println!("{}", game.state.get_status_text()),
         Status::Turn { .. } => println!("The game cannot end from here. Tie!"),
     };
 }
@@ -0,0 +1,53 @@
+use crate::{state, state_space};
+
+/// Number of leaf nodes `depth` plies below `state` for testing move generation and undoing
+/// actions. Games that end before `depth` contribute no leaves.
+pub fn perft<const N: usize, T: state_space::StateSpace<N>>(
+    state: &state::State<N, T>,
+    depth: usize,
+) -> u64 {
+    let mut state = state.clone();
+    count_leaves(&mut state, depth)
+}
+
+/// Leaves below `state` after which `state` is restored
+fn count_leaves<const N: usize, T: state_space::StateSpace<N>>(
+    state: &mut state::State<N, T>,
+    depth: usize,
+) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    if state.is_terminal() {
+        return 0;
+    }
+    let actions: Vec<_> = state.iter_actions().collect();
+    if depth == 1 {
+        return actions.len() as u64;
+    }
+    let mut leaves = 0;
+    for action in actions {
+        #[cfg(debug_assertions)]
+        let before = (state.get_i(), state.get_abbreviation());
+        state.play_action(&action).expect("valid action");
+        leaves += count_leaves(state, depth - 1);
+        state.undo_action(&action).expect("valid action");
+        #[cfg(debug_assertions)]
+        assert_eq!((state.get_i(), state.get_abbreviation()), before);
+    }
+    leaves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn initial_state() {
+        // Swapping hands is not a split so only attacks are possible until a player has 4 fingers
+        let state = Chopsticks.get_initial_state();
+        let counts: Vec<_> = (0..=3).map(|depth| perft(&state, depth)).collect();
+        assert_eq!(counts, [1, 4, 16, 72]);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::state_space::chopsticks::Chopsticks;
+use crate::strategies::{minimax, pure_monte_carlo, random, Strategy};
+
+/// Simulations per action of `Difficulty::Medium`
+const MEDIUM_SIMS: usize = 20;
+
+/// Plies searched per action of `Difficulty::Hard`
+const HARD_DEPTH: usize = 8;
+
+/// Computer opponents for casual two player chopsticks from weakest to strongest
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    /// Plays uniformly random actions
+    Easy,
+
+    /// Plays the action winning the most of a few random playouts
+    Medium,
+
+    /// Plays the best action of a deep minimax search
+    Hard,
+}
+
+impl Difficulty {
+    /// A fresh strategy playing at this level
+    pub fn strategy(&self) -> Box<dyn Strategy<2, Chopsticks>> {
+        match self {
+            Difficulty::Easy => Box::new(random::Random::default()),
+            Difficulty::Medium => Box::new(pure_monte_carlo::PureMonteCarlo::new(MEDIUM_SIMS)),
+            Difficulty::Hard => Box::new(minimax::Minimax::new(HARD_DEPTH)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::StateSpace;
+
+    #[test]
+    fn legal_actions() {
+        let state = Chopsticks.get_initial_state();
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let action = difficulty.strategy().get_action(&state);
+            assert!(state.iter_actions().any(|legal| legal == action));
+        }
+    }
+}
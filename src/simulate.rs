@@ -0,0 +1,105 @@
+use crate::strategies::Strategy;
+use crate::{state, state_space, tournament};
+
+/// Builds the two strategies of a single game
+pub type StrategyPair<T> = (Box<dyn Strategy<2, T>>, Box<dyn Strategy<2, T>>);
+
+/// Tallies of many games between two strategies where index `k` is the `k`th strategy of each
+/// `StrategyPair`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AggregateStats {
+    pub wins: [usize; 2],
+    pub draws: usize,
+}
+
+impl AggregateStats {
+    /// Number of games played
+    pub fn get_games(&self) -> usize {
+        self.wins[0] + self.wins[1] + self.draws
+    }
+
+    /// Fraction of games strategy `k` won or 0 if there were none
+    pub fn get_win_rate(&self, k: usize) -> f64 {
+        match self.get_games() {
+            0 => 0.0,
+            games => self.wins[k] as f64 / games as f64,
+        }
+    }
+
+    /// Tallies of both sets of games
+    pub fn merge(self, other: AggregateStats) -> AggregateStats {
+        AggregateStats {
+            wins: [self.wins[0] + other.wins[0], self.wins[1] + other.wins[1]],
+            draws: self.draws + other.draws,
+        }
+    }
+}
+
+/// Plays `games` games from the initial state with a fresh pair of strategies from `factory` for
+/// each, alternating which strategy goes first. Games are ranked by `get_rankings`.
+pub fn run_many<T, F>(factory: F, games: usize) -> AggregateStats
+where
+    T: state_space::StateSpace<2> + std::fmt::Debug,
+    F: Fn() -> StrategyPair<T>,
+{
+    (0..games)
+        .map(|g| play_game(&factory, g))
+        .fold(AggregateStats::default(), AggregateStats::merge)
+}
+
+/// `run_many` with games spread across threads
+#[cfg(feature = "parallel")]
+pub fn run_many_parallel<T, F>(factory: F, games: usize) -> AggregateStats
+where
+    T: state_space::StateSpace<2> + std::fmt::Debug,
+    F: Fn() -> StrategyPair<T> + Sync,
+{
+    use rayon::prelude::*;
+    (0..games)
+        .into_par_iter()
+        .map(|g| play_game(&factory, g))
+        .reduce(AggregateStats::default, AggregateStats::merge)
+}
+
+/// Tallies of game `g` where the second strategy goes first in odd games
+fn play_game<T, F>(factory: &F, g: usize) -> AggregateStats
+where
+    T: state_space::StateSpace<2> + std::fmt::Debug,
+    F: Fn() -> StrategyPair<T>,
+{
+    let (mut strategy_0, mut strategy_1) = factory();
+    let mut stats = AggregateStats::default();
+    let strategies: [&mut dyn Strategy<2, T>; 2] = [&mut *strategy_0, &mut *strategy_1];
+    match tournament::play_seated(state::State::default(), strategies, g) {
+        Some(k) => stats.wins[k] += 1,
+        None => stats.draws += 1,
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::chopsticks::Chopsticks;
+    use crate::strategies::{negamax, random};
+
+    fn factory() -> StrategyPair<Chopsticks> {
+        (
+            Box::new(negamax::Negamax::new(4)),
+            Box::new(random::Random::seeded(0)),
+        )
+    }
+
+    #[test]
+    fn many() {
+        let stats = run_many(factory, 6);
+        assert_eq!(stats.get_games(), 6);
+        assert_eq!(stats.wins[1], 0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn many_parallel() {
+        assert_eq!(run_many_parallel(factory, 6), run_many(factory, 6));
+    }
+}
@@ -0,0 +1,590 @@
+use crate::cache;
+use crate::state::{self, status::Status};
+use crate::state_space::StateSpace;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Game-theoretic value of a `State` for its current player `i` under perfect play
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    /// The same value seen by the other player
+    pub fn flip(self) -> Outcome {
+        match self {
+            Outcome::Win => Outcome::Loss,
+            Outcome::Loss => Outcome::Win,
+            Outcome::Draw => Outcome::Draw,
+        }
+    }
+}
+
+/// Outcome of every `State` reachable from the initial state keyed by `serialize_state` with the
+/// number of plies until the game is over when the winner wins as quickly and the loser loses as
+/// slowly as possible. Values are found by retrograde analysis from the finished games and
+/// positions that never resolve are draws by infinite play with a distance of 0.
+pub fn solve<T: StateSpace<2> + std::fmt::Debug>(space: &T) -> HashMap<u32, (Outcome, u32)> {
+    let initial = space.get_initial_state();
+    let mut turns = HashMap::from([(T::serialize_state(&initial), initial.get_i())]);
+    let mut parents: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut remaining: HashMap<u32, usize> = HashMap::new();
+    let mut outcomes = HashMap::new();
+    let mut resolved = VecDeque::new();
+
+    // Breadth first search for the game graph with finished games as its leaves
+    let mut queue = VecDeque::from([initial]);
+    while let Some(mut state) = queue.pop_front() {
+        let serial = T::serialize_state(&state);
+        if let Status::Over { i: last } = state.get_status() {
+            let outcome = if (last == state.get_i()) != T::MISERE {
+                Outcome::Win
+            } else {
+                Outcome::Loss
+            };
+            outcomes.insert(serial, (outcome, 0));
+            resolved.push_back(serial);
+            continue;
+        }
+        let actions: Vec<_> = state.iter_actions().collect();
+        remaining.insert(serial, actions.len());
+        for action in actions {
+            state.play_action(&action).expect("valid action");
+            let child = T::serialize_state(&state);
+            parents.entry(child).or_default().push(serial);
+            if let std::collections::hash_map::Entry::Vacant(entry) = turns.entry(child) {
+                entry.insert(state.get_i());
+                queue.push_back(state.clone());
+            }
+            state.undo_action(&action).expect("valid action");
+        }
+    }
+
+    // A parent wins with any child lost for it and loses once every child is won for the child.
+    // Children resolve in order of distance so the first lost child is the quickest win and the
+    // last won child is the slowest loss.
+    while let Some(child) = resolved.pop_front() {
+        let (outcome, distance) = outcomes[&child];
+        for &parent in parents.get(&child).into_iter().flatten() {
+            if outcomes.contains_key(&parent) {
+                continue;
+            }
+            let outcome = if turns[&child] == turns[&parent] {
+                outcome
+            } else {
+                outcome.flip()
+            };
+            let count = remaining.get_mut(&parent).expect("parent is not over");
+            *count -= 1;
+            if outcome == Outcome::Win {
+                outcomes.insert(parent, (Outcome::Win, distance + 1));
+                resolved.push_back(parent);
+            } else if *count == 0 {
+                outcomes.insert(parent, (outcome, distance + 1));
+                resolved.push_back(parent);
+            }
+        }
+    }
+
+    for serial in turns.into_keys() {
+        outcomes.entry(serial).or_insert((Outcome::Draw, 0));
+    }
+    outcomes
+}
+
+/// Exact outcome of `state` for its current player and a principal variation. The search is
+/// iteratively deepened minimax over `Loss < Draw < Win` with attacks that kill a hand ordered
+/// first, memoized by canonical serial. A winner never needs to repeat a position so a
+/// game that cannot be decided within as many plies as there are reachable canonical positions
+/// is a draw. The variation ends when the game is over or, for a draw, when a position repeats.
+pub fn prove_value<T: StateSpace<2>>(
+    state: &state::State<2, T>,
+) -> (Outcome, Vec<state::action::Action<2, T>>) {
+    let horizon = state
+        .iter_reachable()
+        .map(|state| state.canonical_serial())
+        .collect::<HashSet<_>>()
+        .len();
+    let mut prover = Prover {
+        table: cache::TranspositionTable::new(usize::MAX),
+    };
+    let mut state = state.clone();
+    let mut depth = 0;
+    let outcome = loop {
+        // Iterative deepening finds decided games without searching undecided lines to the horizon
+        let proof = prover.search(&mut state, depth);
+        if depth >= horizon || matches!(proof, Proof::Decided { .. }) {
+            break proof.get_outcome();
+        }
+        depth += 1;
+    };
+    (outcome, prover.get_principal_variation(state, depth))
+}
+
+/// Result of a depth limited search for the player to move
+#[derive(Copy, Clone, Debug)]
+enum Proof {
+    /// `outcome` is forced and the game is over after `distance` plies
+    Decided { outcome: Outcome, distance: usize },
+
+    /// Neither player can force the game to be over within `depth` plies
+    Undecided { depth: usize },
+}
+
+impl Proof {
+    fn get_outcome(&self) -> Outcome {
+        match *self {
+            Proof::Decided { outcome, .. } => outcome,
+            Proof::Undecided { .. } => Outcome::Draw,
+        }
+    }
+
+    /// The same proof seen by the other player one ply earlier
+    fn get_parent(&self, flip: bool) -> Proof {
+        match *self {
+            Proof::Decided { outcome, distance } => Proof::Decided {
+                outcome: if flip { outcome.flip() } else { outcome },
+                distance: distance + 1,
+            },
+            Proof::Undecided { depth } => Proof::Undecided { depth: depth + 1 },
+        }
+    }
+
+    /// Whether `self` is strictly preferred to `other`. Wins are preferred to undecided games
+    /// which are preferred to losses, taking wins quickly and delaying losses.
+    fn is_better(&self, other: &Proof) -> bool {
+        let rank = |proof: &Proof| match *proof {
+            Proof::Decided {
+                outcome: Outcome::Win,
+                distance,
+            } => (2, -(distance as isize)),
+            Proof::Decided {
+                outcome: Outcome::Loss,
+                distance,
+            } => (0, distance as isize),
+            _ => (1, 0),
+        };
+        rank(self) > rank(other)
+    }
+}
+
+/// Depth limited minimax search with proofs memoized by canonical serial
+struct Prover {
+    table: cache::TranspositionTable<Proof>,
+}
+
+impl Prover {
+    /// Proof for the player to move looking `depth` plies ahead. Actions are only cut off by a
+    /// win on the next ply since no win is quicker, so a decided win is always the quickest.
+    fn search<T: StateSpace<2>>(&mut self, state: &mut state::State<2, T>, depth: usize) -> Proof {
+        if let Status::Over { i: last } = state.get_status() {
+            return Proof::Decided {
+                outcome: get_over_outcome(state, last),
+                distance: 0,
+            };
+        }
+        let serial = state.canonical_serial();
+        match self.table.get(serial) {
+            Some(&proof @ Proof::Decided { .. }) => return proof,
+            Some(&Proof::Undecided { depth: searched }) if searched >= depth => {
+                return Proof::Undecided { depth }
+            }
+            _ if depth == 0 => return Proof::Undecided { depth },
+            _ => {}
+        }
+        let i = state.get_i();
+        let mut best: Option<Proof> = None;
+        for action in get_ordered_actions(state) {
+            state.play_action(&action).expect("valid action");
+            let proof = self.search(state, depth - 1).get_parent(state.get_i() != i);
+            state.undo_action(&action).expect("valid action");
+            best = match best {
+                Some(best) if !proof.is_better(&best) => Some(best),
+                _ => Some(proof),
+            };
+            if let Proof::Decided {
+                outcome: Outcome::Win,
+                distance: 1,
+            } = proof
+            {
+                break;
+            }
+        }
+        let best = best.expect("multiple actions");
+        self.table.insert(serial, best);
+        best
+    }
+
+    /// Follows the best action from `state` until the game is over or a position repeats
+    fn get_principal_variation<T: StateSpace<2>>(
+        &mut self,
+        mut state: state::State<2, T>,
+        depth: usize,
+    ) -> Vec<state::action::Action<2, T>> {
+        let mut variation = Vec::new();
+        let mut seen = HashSet::new();
+        while !state.is_terminal() && seen.insert(state.canonical_serial()) {
+            let i = state.get_i();
+            let mut best: Option<(Proof, state::action::Action<2, T>)> = None;
+            for action in get_ordered_actions(&state) {
+                state.play_action(&action).expect("valid action");
+                let proof = self
+                    .search(&mut state, depth)
+                    .get_parent(state.get_i() != i);
+                state.undo_action(&action).expect("valid action");
+                best = match best {
+                    Some((best, best_action)) if !proof.is_better(&best) => {
+                        Some((best, best_action))
+                    }
+                    _ => Some((proof, action)),
+                };
+            }
+            let (_, action) = best.expect("multiple actions");
+            state.play_action(&action).expect("valid action");
+            variation.push(action);
+        }
+        variation
+    }
+}
+
+/// Outcome for the player to move in a finished game where `last` is the last player standing
+fn get_over_outcome<T: StateSpace<2>>(state: &state::State<2, T>, last: usize) -> Outcome {
+    if (last == state.get_i()) != T::MISERE {
+        Outcome::Win
+    } else {
+        Outcome::Loss
+    }
+}
+
+/// Legal actions with attacks that kill a hand first
+fn get_ordered_actions<T: StateSpace<2>>(
+    state: &state::State<2, T>,
+) -> Vec<state::action::Action<2, T>> {
+    let mut actions: Vec<_> = state.iter_actions().collect();
+    actions.sort_by_key(|action| match *action {
+        state::action::Action::Attack { i, j, a, b } => {
+            let attacker = state.get_players()[i].hands[a];
+            T::resolve_attack(state.get_players()[j].hands[b], attacker, b) != 0
+        }
+        _ => true,
+    });
+    actions
+}
+
+/// Outcome of `state` for its current player from a table produced by `solve`
+pub fn get_outcome<T: StateSpace<2>>(
+    outcomes: &HashMap<u32, (Outcome, u32)>,
+    state: &state::State<2, T>,
+) -> Option<Outcome> {
+    get_solution(outcomes, state).map(|(outcome, _)| outcome)
+}
+
+/// Outcome of `state` for its current player and plies until the game is over from a table
+/// produced by `solve`
+pub fn get_solution<T: StateSpace<2>>(
+    outcomes: &HashMap<u32, (Outcome, u32)>,
+    state: &state::State<2, T>,
+) -> Option<(Outcome, u32)> {
+    outcomes.get(&T::serialize_state(state)).copied()
+}
+
+/// A quickest winning line from `start` against the slowest defense when its current player has
+/// a forced win according to a table produced by `solve`, with as many actions as its distance
+pub fn shortest_win<T: StateSpace<2>>(
+    outcomes: &HashMap<u32, (Outcome, u32)>,
+    start: &state::State<2, T>,
+) -> Option<Vec<state::action::Action<2, T>>> {
+    let winner = start.get_i();
+    let (Outcome::Win, mut distance) = get_solution(outcomes, start)? else {
+        return None;
+    };
+    let mut state = start.clone();
+    let mut line = Vec::new();
+    while distance > 0 {
+        let actions: Vec<_> = state.iter_actions().collect();
+        let action = actions.into_iter().find(|action| {
+            state.play_action(action).expect("valid action");
+            let child = get_solution(outcomes, &state).map(|(outcome, child_distance)| {
+                let outcome = if state.get_i() == winner {
+                    outcome
+                } else {
+                    outcome.flip()
+                };
+                (outcome, child_distance)
+            });
+            state.undo_action(action).expect("valid action");
+            child == Some((Outcome::Win, distance - 1))
+        })?;
+        state.play_action(&action).expect("valid action");
+        line.push(action);
+        distance -= 1;
+    }
+    Some(line)
+}
+
+/// A quickest decisive line from the initial state of `space` when either player can force a
+/// result, the winner winning as quickly and the loser losing as slowly as possible, or `None`
+/// if the game is a draw under perfect play
+pub fn shortest_decisive_line<T: StateSpace<2> + std::fmt::Debug>(
+    space: &T,
+) -> Option<Vec<state::action::Action<2, T>>> {
+    let outcomes = solve(space);
+    let mut state = space.get_initial_state();
+    let mut line = Vec::new();
+    if let (Outcome::Loss, distance) = get_solution(&outcomes, &state)? {
+        // The loser moves first so their slowest loss leads to a forced win for the other player
+        let i = state.get_i();
+        let actions: Vec<_> = state.iter_actions().collect();
+        let action = actions.into_iter().find(|action| {
+            state.play_action(action).expect("valid action");
+            let child = get_solution(&outcomes, &state).map(|(outcome, child_distance)| {
+                let outcome = if state.get_i() == i {
+                    outcome
+                } else {
+                    outcome.flip()
+                };
+                (outcome, child_distance)
+            });
+            state.undo_action(action).expect("valid action");
+            child == Some((Outcome::Loss, distance - 1))
+        })?;
+        state.play_action(&action).expect("valid action");
+        line.push(action);
+        if state.is_terminal() {
+            return Some(line);
+        }
+    }
+    line.extend(shortest_win(&outcomes, &state)?);
+    Some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game;
+    use crate::state_space::{chopsticks::Chopsticks, misere_chopsticks::MisereChopsticks};
+
+    fn outcome(outcomes: &HashMap<u32, (Outcome, u32)>, abbreviation: &str) -> Option<Outcome> {
+        let state = state::State::<2, Chopsticks>::from_abbreviation(abbreviation).expect("valid");
+        get_outcome(outcomes, &state)
+    }
+
+    /// Hand-picked `Chopsticks` positions with player 0 to move and their game-theoretic values:
+    /// the initial position, a position won in one ply and a position that loops forever
+    const KNOWN_VALUES: [(&str, Outcome); 3] = [
+        ("1111", Outcome::Draw),
+        ("1410", Outcome::Win),
+        ("2323", Outcome::Draw),
+    ];
+
+    #[test]
+    fn known_values() {
+        let outcomes = solve(&Chopsticks);
+        for (abbreviation, expected) in KNOWN_VALUES {
+            let state =
+                state::State::<2, Chopsticks>::from_abbreviation(abbreviation).expect("valid");
+            let serial = Chopsticks::serialize_state(&state);
+            let round_trip = Chopsticks::deserialize_state(serial).expect("valid serial");
+            assert_eq!(round_trip.get_abbreviation(), abbreviation);
+            assert_eq!(
+                outcome(&outcomes, abbreviation),
+                Some(expected),
+                "{abbreviation}"
+            );
+            assert_eq!(prove_value(&state).0, expected, "{abbreviation}");
+        }
+    }
+
+    #[test]
+    fn consistent() {
+        let outcomes = solve(&Chopsticks);
+        for (&serial, &(expected, _)) in outcomes.iter() {
+            let mut state = Chopsticks::deserialize_state(serial).expect("valid serial");
+            if let Status::Over { i: _ } = state.get_status() {
+                continue;
+            }
+            let i = state.get_i();
+            let actions: Vec<_> = state.iter_actions().collect();
+            let children: Vec<_> = actions
+                .iter()
+                .map(|action| {
+                    state.play_action(action).expect("valid action");
+                    let (child, _) = outcomes[&Chopsticks::serialize_state(&state)];
+                    let child = if state.get_i() == i {
+                        child
+                    } else {
+                        child.flip()
+                    };
+                    state.undo_action(action).expect("valid action");
+                    child
+                })
+                .collect();
+            let best = if children.contains(&Outcome::Win) {
+                Outcome::Win
+            } else if children.contains(&Outcome::Draw) {
+                Outcome::Draw
+            } else {
+                Outcome::Loss
+            };
+            assert_eq!(best, expected);
+        }
+    }
+
+    #[test]
+    fn distances() {
+        let outcomes = solve(&Chopsticks);
+        let mut state = state::State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        assert_eq!(get_solution(&outcomes, &state), Some((Outcome::Win, 1)));
+        assert!(state.play_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(get_solution(&outcomes, &state), Some((Outcome::Win, 0)));
+        let state = Chopsticks.get_initial_state();
+        assert_eq!(get_solution(&outcomes, &state), Some((Outcome::Draw, 0)));
+    }
+
+    #[test]
+    fn shortest_win() {
+        let outcomes = solve(&Chopsticks);
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        let line = super::shortest_win(&outcomes, &state).expect("forced win");
+        assert_eq!(line.len(), 1);
+        assert_eq!(
+            super::shortest_win(&outcomes, &Chopsticks.get_initial_state()),
+            None
+        );
+
+        // The longest forced win among the reachable positions
+        let (&serial, &(_, distance)) = outcomes
+            .iter()
+            .filter(|(_, (outcome, _))| *outcome == Outcome::Win)
+            .max_by_key(|(&serial, &(_, distance))| (distance, std::cmp::Reverse(serial)))
+            .expect("won position");
+        let state = Chopsticks::deserialize_state(serial).expect("valid serial");
+        let line = super::shortest_win(&outcomes, &state).expect("forced win");
+        assert_eq!(line.len(), distance as usize);
+        let end = game::replay(state.clone(), &line).expect("valid line");
+        assert_eq!(end.get_winner(), Some(state.get_i()));
+    }
+
+    /// One hand that dies at exactly 5 fingers, won by the first player
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct OneHanded;
+
+    impl StateSpace<2> for OneHanded {
+        type Hands = [u32; 1];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    /// One hand that dies at exactly 3 fingers, won by the second player
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct OneHandedThree;
+
+    impl StateSpace<2> for OneHandedThree {
+        type Hands = [u32; 1];
+        const ROLLOVER: u32 = 3;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn shortest_decisive_line() {
+        assert_eq!(super::shortest_decisive_line(&Chopsticks), None);
+
+        let line = super::shortest_decisive_line(&OneHanded).expect("first player wins");
+        let notation: Vec<_> = line.iter().map(|action| action.to_notation()).collect();
+        assert_eq!(notation, ["a 0 1 0 0", "a 1 0 0 0", "a 0 1 0 0"]);
+        let end = game::replay(OneHanded.get_initial_state(), &line).expect("valid line");
+        assert_eq!(end.get_winner(), Some(0));
+
+        let line = super::shortest_decisive_line(&OneHandedThree).expect("second player wins");
+        assert_eq!(line.len(), 2);
+        let end = game::replay(OneHandedThree.get_initial_state(), &line).expect("valid line");
+        assert_eq!(end.get_winner(), Some(1));
+    }
+
+    #[test]
+    fn misere_finished_game() {
+        let outcomes = solve(&MisereChopsticks);
+        let mut state =
+            state::State::<2, MisereChopsticks>::from_abbreviation("1410").expect("valid");
+        assert!(state.play_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(get_outcome(&outcomes, &state), Some(Outcome::Loss));
+        let outcomes = solve(&Chopsticks);
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1400").expect("valid");
+        assert_eq!(get_outcome(&outcomes, &state), Some(Outcome::Win));
+    }
+
+    #[test]
+    fn prove_won_position() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        let (outcome, variation) = prove_value(&state);
+        assert_eq!(outcome, Outcome::Win);
+        let end = game::replay(state, &variation).expect("valid variation");
+        assert_eq!(end.get_winner(), Some(0));
+    }
+
+    #[test]
+    fn prove_initial_state() {
+        let state = Chopsticks.get_initial_state();
+        let (outcome, variation) = prove_value(&state);
+        assert_eq!(outcome, Outcome::Draw);
+        let end = game::replay(state.clone(), &variation).expect("valid variation");
+        assert!(!end.is_terminal());
+        let repeated = (0..variation.len()).any(|k| {
+            let before = game::replay(state.clone(), &variation[..k]).expect("valid variation");
+            before.canonical_serial() == end.canonical_serial()
+        });
+        assert!(repeated);
+    }
+
+    #[test]
+    fn quickest_proofs() {
+        let outcomes = solve(&Chopsticks);
+        let mut decided: Vec<_> = outcomes
+            .iter()
+            .filter(|(_, (outcome, _))| *outcome != Outcome::Draw)
+            .map(|(&serial, &solution)| (serial, solution))
+            .collect();
+        decided.sort_unstable_by_key(|&(serial, _)| serial);
+        for (serial, (expected, expected_distance)) in decided {
+            let mut state = Chopsticks::deserialize_state(serial).expect("valid serial");
+            let mut prover = Prover {
+                table: cache::TranspositionTable::new(usize::MAX),
+            };
+            let proof = (0..)
+                .map(|depth| prover.search(&mut state, depth))
+                .find(|proof| matches!(proof, Proof::Decided { .. }))
+                .expect("decided");
+            let Proof::Decided { outcome, distance } = proof else {
+                unreachable!()
+            };
+            assert_eq!((outcome, distance), (expected, expected_distance as usize));
+        }
+    }
+
+    #[test]
+    fn prove_value_matches_solve() {
+        let outcomes = solve(&Chopsticks);
+        let mut serials: Vec<_> = outcomes.keys().copied().collect();
+        serials.sort_unstable();
+        let (draws, decided): (Vec<_>, Vec<_>) = serials
+            .into_iter()
+            .partition(|serial| outcomes[serial].0 == Outcome::Draw);
+        for serial in draws
+            .into_iter()
+            .step_by(472)
+            .chain(decided.into_iter().step_by(3))
+        {
+            let (expected, distance) = outcomes[&serial];
+            let state = Chopsticks::deserialize_state(serial).expect("valid serial");
+            let (outcome, variation) = prove_value(&state);
+            assert_eq!(outcome, expected, "{}", state.get_abbreviation());
+            if outcome != Outcome::Draw {
+                assert_eq!(variation.len(), distance as usize);
+            }
+            let end = game::replay(state, &variation).expect("valid variation");
+            assert_eq!(end.is_terminal(), outcome != Outcome::Draw);
+        }
+    }
+}
@@ -1,8 +1,15 @@
-use crate::{state, state_space};
+use crate::state_space;
 use std::marker::PhantomData;
 
-/// Chopsticks 'move'
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// Chopsticks 'move'. Actions are totally ordered with every `Attack` before every `Split` and
+/// every `Split` before every `Pass`, attacks by `(i, j, a, b)`, splits by `i` then `hands_0`
+/// then `hands_1`, each lexicographically, and passes by `i`.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
 pub enum Action<const N: usize, T: state_space::StateSpace<N>> {
     Attack {
         i: usize,
@@ -12,16 +19,52 @@ pub enum Action<const N: usize, T: state_space::StateSpace<N>> {
     },
     Split {
         i: usize,
-        hands_0: [u32; state::N_HANDS],
-        hands_1: [u32; state::N_HANDS],
+        #[cfg_attr(feature = "serde", serde(with = "crate::state::serde_hands"))]
+        hands_0: T::Hands,
+        #[cfg_attr(feature = "serde", serde(with = "crate::state::serde_hands"))]
+        hands_1: T::Hands,
     },
+    /// Player `i` ends their turn without changing any hands if `ALLOW_PASS` or they have no
+    /// other action under `RolloverMode::Cutoff`
+    Pass { i: usize },
+    #[cfg_attr(feature = "serde", serde(skip))]
     Phantom(PhantomData<T>),
 }
 
+/// What `State::play_action_reversible` changed so `State::undo_with` can restore it exactly
+#[derive(Copy, Clone, Debug)]
+pub struct ReversibleToken<const N: usize, T: state_space::StateSpace<N>> {
+    /// Turn before the action
+    pub(super) i: usize,
+
+    /// Index of the only player whose hands changed
+    pub(super) player: usize,
+
+    /// Hands of `player` before the action
+    pub(super) hands: T::Hands,
+}
+
+/// Legal actions of a `State` split by kind, from `State::legal_actions`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LegalActions<const N: usize, T: state_space::StateSpace<N>> {
+    /// `Action::Attack`s in the order of `State::iter_attack_actions`
+    pub attacks: Vec<Action<N, T>>,
+
+    /// `Action::Split`s in the order of `State::iter_split_actions`
+    pub splits: Vec<Action<N, T>>,
+
+    /// `Action::Pass` if the `StateSpace` allows passing
+    pub pass: Option<Action<N, T>>,
+}
+
 #[derive(Debug)]
 pub enum ActionError {
     GameIsOver,
     WrongTurn,
+    /// `State::play_simultaneous` only resolves attacks that leave a player standing
+    NotSimultaneous,
+    /// The `StateSpace` does not `ALLOW_PASS` and the pass is not forced
+    PassNotAllowed,
     AttackError(AttackError),
     SplitError(SplitError),
 }
@@ -32,6 +75,8 @@ pub enum AttackError {
     HandIndexOutOfBounds,
     HandIsNotAlive,
     PlayerAttackSelf,
+    ExceedsRollover,
+    HandValueNotFound,
 }
 
 #[derive(Debug)]
@@ -43,16 +88,258 @@ pub enum SplitError {
     InvalidFingerValue,
 }
 
+/// Why a string is not an `Action`
+#[derive(Debug)]
+pub enum ParseActionError {
+    Empty,
+    UnknownKind,
+    InvalidNumber,
+    WrongTokenCount,
+}
+
+impl std::fmt::Display for ActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionError::GameIsOver => write!(f, "the game is over"),
+            ActionError::WrongTurn => write!(f, "it is not the player's turn"),
+            ActionError::NotSimultaneous => write!(f, "actions cannot resolve simultaneously"),
+            ActionError::PassNotAllowed => write!(f, "passing is not allowed"),
+            ActionError::AttackError(_) => write!(f, "invalid attack"),
+            ActionError::SplitError(_) => write!(f, "invalid split"),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ActionError::AttackError(error) => Some(error),
+            ActionError::SplitError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AttackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttackError::PlayerIndexOutOfBounds => write!(f, "player index is out of bounds"),
+            AttackError::HandIndexOutOfBounds => write!(f, "hand index is out of bounds"),
+            AttackError::HandIsNotAlive => write!(f, "hand is not alive"),
+            AttackError::PlayerAttackSelf => write!(f, "player cannot attack themself"),
+            AttackError::ExceedsRollover => write!(f, "attack exceeds rollover"),
+            AttackError::HandValueNotFound => write!(f, "no hand has that many fingers"),
+        }
+    }
+}
+
+impl std::error::Error for AttackError {}
+
+impl std::fmt::Display for SplitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SplitError::ImproperContext => write!(f, "hands do not match the player's hands"),
+            SplitError::MoveWithoutChange => write!(f, "split does not change the hands"),
+            SplitError::InvalidHandLen => write!(f, "wrong number of hands"),
+            SplitError::InvalidTotalFingers => write!(f, "split changes the total fingers"),
+            SplitError::InvalidFingerValue => write!(f, "hand is not between 1 and rollover"),
+        }
+    }
+}
+
+impl std::error::Error for SplitError {}
+
+impl std::fmt::Display for ParseActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseActionError::Empty => write!(f, "action is empty"),
+            ParseActionError::UnknownKind => write!(f, "action is not 'a', 's' or 'p'"),
+            ParseActionError::InvalidNumber => write!(f, "token is not a number"),
+            ParseActionError::WrongTokenCount => write!(f, "wrong number of tokens"),
+        }
+    }
+}
+
+impl std::error::Error for ParseActionError {}
+
+/// Compares fields directly so `Action` is `Eq` even when the `StateSpace` is not
+impl<const N: usize, T: state_space::StateSpace<N>> PartialEq for Action<N, T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Action::Attack { i, j, a, b },
+                Action::Attack {
+                    i: other_i,
+                    j: other_j,
+                    a: other_a,
+                    b: other_b,
+                },
+            ) => (i, j, a, b) == (other_i, other_j, other_a, other_b),
+            (
+                Action::Split {
+                    i,
+                    hands_0,
+                    hands_1,
+                },
+                Action::Split {
+                    i: other_i,
+                    hands_0: other_hands_0,
+                    hands_1: other_hands_1,
+                },
+            ) => (i, hands_0, hands_1) == (other_i, other_hands_0, other_hands_1),
+            (Action::Pass { i }, Action::Pass { i: other_i }) => i == other_i,
+            (Action::Phantom(_), Action::Phantom(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Eq for Action<N, T> {}
+
+impl<const N: usize, T: state_space::StateSpace<N>> PartialOrd for Action<N, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Ord for Action<N, T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (
+                Action::Attack { i, j, a, b },
+                Action::Attack {
+                    i: other_i,
+                    j: other_j,
+                    a: other_a,
+                    b: other_b,
+                },
+            ) => (i, j, a, b).cmp(&(other_i, other_j, other_a, other_b)),
+            (
+                Action::Split {
+                    i,
+                    hands_0,
+                    hands_1,
+                },
+                Action::Split {
+                    i: other_i,
+                    hands_0: other_hands_0,
+                    hands_1: other_hands_1,
+                },
+            ) => (i, hands_0.as_ref(), hands_1.as_ref()).cmp(&(
+                other_i,
+                other_hands_0.as_ref(),
+                other_hands_1.as_ref(),
+            )),
+            (Action::Pass { i }, Action::Pass { i: other_i }) => i.cmp(other_i),
+            (Action::Phantom(_), _) | (_, Action::Phantom(_)) => panic!("expect not phantom"),
+            _ => self.get_kind_rank().cmp(&other.get_kind_rank()),
+        }
+    }
+}
+
 impl<const N: usize, T: state_space::StateSpace<N>> Action<N, T> {
     pub fn get_i(&self) -> usize {
         match self {
             Action::Split { i, .. } => *i,
             Action::Attack { i, .. } => *i,
+            Action::Pass { i } => *i,
+            Action::Phantom(_) => panic!("expect not phantom"),
+        }
+    }
+
+    /// Position of the variant in the order of `Ord`
+    fn get_kind_rank(&self) -> u8 {
+        match self {
+            Action::Attack { .. } => 0,
+            Action::Split { .. } => 1,
+            Action::Pass { .. } => 2,
             Action::Phantom(_) => panic!("expect not phantom"),
         }
     }
 }
 
+impl<const N: usize, T: state_space::StateSpace<N>> Action<N, T> {
+    /// Tokens in the form parsed by `FromStr`, such as `a 0 1 0 1`, `s 0 1 3 2 2` or `p 0`
+    pub fn to_notation(&self) -> String {
+        match self {
+            Action::Attack { i, j, a, b } => format!("a {i} {j} {a} {b}"),
+            Action::Split {
+                i,
+                hands_0,
+                hands_1,
+            } => {
+                let hands = hands_0.as_ref().iter().chain(hands_1.as_ref().iter());
+                let hands: Vec<_> = hands.map(|h| h.to_string()).collect();
+                format!("s {i} {}", hands.join(" "))
+            }
+            Action::Pass { i } => format!("p {i}"),
+            Action::Phantom(_) => panic!("expect not phantom"),
+        }
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> std::fmt::Display for Action<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Attack { i, j, a, b } => write!(f, "P{i} h{a} -> P{j} h{b}"),
+            Action::Split {
+                i,
+                hands_0,
+                hands_1,
+            } => {
+                let hands_0: String = hands_0.as_ref().iter().map(|h| h.to_string()).collect();
+                let hands_1: String = hands_1.as_ref().iter().map(|h| h.to_string()).collect();
+                write!(f, "P{i} split {hands_0} -> {hands_1}")
+            }
+            Action::Pass { i } => write!(f, "P{i} pass"),
+            Action::Phantom(_) => panic!("expect not phantom"),
+        }
+    }
+}
+
+/// Parses whitespace separated tokens of the form
+///
+/// - `a <i> <j> <a> <b>` for `Action::Attack`, such as `a 0 1 0 1`
+/// - `s <i> <hands_0> <hands_1>` for `Action::Split` where both hands are `T::N_HANDS` finger
+///   counts, such as `s 0 1 3 2 2`
+/// - `p <i>` for `Action::Pass`, such as `p 0`
+///
+/// Without a state the acting player and split hands cannot be inferred, see `State::parse_action`
+impl<const N: usize, T: state_space::StateSpace<N>> std::str::FromStr for Action<N, T> {
+    type Err = ParseActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let kind = tokens.next().ok_or(ParseActionError::Empty)?;
+        let numbers = tokens
+            .map(|token| token.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseActionError::InvalidNumber)?;
+        match (kind, numbers.as_slice()) {
+            ("a", &[i, j, a, b]) => Ok(Action::Attack { i, j, a, b }),
+            ("s", [i, hands @ ..]) if hands.len() == 2 * T::N_HANDS => {
+                let mut hands_0 = T::Hands::default();
+                let mut hands_1 = T::Hands::default();
+                let (fingers_0, fingers_1) = hands.split_at(T::N_HANDS);
+                for (hand, &fingers) in hands_0.as_mut().iter_mut().zip(fingers_0) {
+                    *hand = u32::try_from(fingers).map_err(|_| ParseActionError::InvalidNumber)?;
+                }
+                for (hand, &fingers) in hands_1.as_mut().iter_mut().zip(fingers_1) {
+                    *hand = u32::try_from(fingers).map_err(|_| ParseActionError::InvalidNumber)?;
+                }
+                Ok(Action::Split {
+                    i: *i,
+                    hands_0,
+                    hands_1,
+                })
+            }
+            ("p", &[i]) => Ok(Action::Pass { i }),
+            ("a" | "s" | "p", _) => Err(ParseActionError::WrongTokenCount),
+            _ => Err(ParseActionError::UnknownKind),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +367,149 @@ mod tests {
         };
         assert_eq!(action.get_i(), i);
     }
+
+    #[test]
+    fn display_attack() {
+        let action = Action::Attack::<2, Chopsticks> {
+            i: 0,
+            j: 1,
+            a: 0,
+            b: 1,
+        };
+        assert_eq!(action.to_string(), "P0 h0 -> P1 h1");
+    }
+
+    #[test]
+    fn display_split() {
+        let action = Action::Split::<2, Chopsticks> {
+            i: 0,
+            hands_0: [1, 3],
+            hands_1: [2, 2],
+        };
+        assert_eq!(action.to_string(), "P0 split 13 -> 22");
+    }
+
+    #[test]
+    fn parse_attack() {
+        let action: Action<2, Chopsticks> = "a 0 1 0 1".parse().expect("valid");
+        assert_eq!(
+            action,
+            Action::Attack {
+                i: 0,
+                j: 1,
+                a: 0,
+                b: 1
+            }
+        );
+    }
+
+    #[test]
+    fn parse_split() {
+        let action: Action<2, Chopsticks> = " s 1  1 3 2 2 ".parse().expect("valid");
+        assert_eq!(
+            action,
+            Action::Split {
+                i: 1,
+                hands_0: [1, 3],
+                hands_1: [2, 2]
+            }
+        );
+    }
+
+    #[test]
+    fn sorted_actions() {
+        let state =
+            crate::state::State::<2, Chopsticks>::with_hands([[2, 2], [1, 3]], 1).expect("valid");
+        let mut actions: Vec<_> = state.iter_actions().collect();
+        actions.sort();
+        let notation: Vec<_> = actions.iter().map(|action| action.to_notation()).collect();
+        assert_eq!(
+            notation,
+            [
+                "a 1 0 0 0",
+                "a 1 0 0 1",
+                "a 1 0 1 0",
+                "a 1 0 1 1",
+                "s 1 1 3 2 2",
+            ]
+        );
+        let mut reversed: Vec<_> = actions.iter().rev().copied().collect();
+        reversed.sort();
+        assert_eq!(reversed, actions);
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let attack = Action::<2, Chopsticks>::Attack {
+            i: 0,
+            j: 1,
+            a: 0,
+            b: 1,
+        };
+        assert_eq!(attack.to_notation(), "a 0 1 0 1");
+        let parsed: Action<2, Chopsticks> = attack.to_notation().parse().expect("valid notation");
+        assert_eq!(parsed, attack);
+        let split = Action::<2, Chopsticks>::Split {
+            i: 1,
+            hands_0: [1, 3],
+            hands_1: [2, 2],
+        };
+        assert_eq!(split.to_notation(), "s 1 1 3 2 2");
+        let parsed: Action<2, Chopsticks> = split.to_notation().parse().expect("valid notation");
+        assert_eq!(parsed, split);
+        let pass = Action::<2, Chopsticks>::Pass { i: 1 };
+        assert_eq!(pass.to_notation(), "p 1");
+        assert_eq!(pass.to_string(), "P1 pass");
+        let parsed: Action<2, Chopsticks> = pass.to_notation().parse().expect("valid notation");
+        assert_eq!(parsed, pass);
+        assert!(split < pass);
+    }
+
+    #[test]
+    fn parse_malformed() {
+        let parse = |s: &str| s.parse::<Action<2, Chopsticks>>();
+        assert!(matches!(parse(""), Err(ParseActionError::Empty)));
+        assert!(matches!(parse("x 0 1"), Err(ParseActionError::UnknownKind)));
+        assert!(matches!(
+            parse("a 0 1 0 -1"),
+            Err(ParseActionError::InvalidNumber)
+        ));
+        assert!(matches!(
+            parse("a 0 1 0"),
+            Err(ParseActionError::WrongTokenCount)
+        ));
+        assert!(matches!(
+            parse("s 0 2 2"),
+            Err(ParseActionError::WrongTokenCount)
+        ));
+        assert!(matches!(
+            parse("p 0 1"),
+            Err(ParseActionError::WrongTokenCount)
+        ));
+    }
+
+    #[test]
+    fn action_error_source() {
+        let error: Box<dyn std::error::Error> =
+            Box::new(ActionError::AttackError(AttackError::HandIsNotAlive));
+        assert_eq!(error.to_string(), "invalid attack");
+        let source = error.source().expect("attack error");
+        assert_eq!(source.to_string(), "hand is not alive");
+        assert!(source.downcast_ref::<AttackError>().is_some());
+        assert!(std::error::Error::source(&ActionError::WrongTurn).is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let action = Action::Split::<2, Chopsticks> {
+            i: 0,
+            hands_0: [1, 3],
+            hands_1: [2, 2],
+        };
+        let json = serde_json::to_string(&action).expect("serializable");
+        assert_eq!(json, r#"{"Split":{"i":0,"hands_0":[1,3],"hands_1":[2,2]}}"#);
+        let deserialized: Action<2, Chopsticks> = serde_json::from_str(&json).expect("valid");
+        assert_eq!(deserialized, action);
+    }
 }
@@ -1,38 +1,221 @@
-use crate::state_space::StateSpace;
+use crate::state_space::{self, Hands, RolloverMode, StateSpace};
 use itertools::Itertools;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub mod action;
 pub mod player;
 pub mod status;
 
-/// Number of hands per player
-pub const N_HANDS: usize = 2;
-
 /// Game state for [chopsticks](https://en.wikipedia.org/wiki/Chopsticks_(hand_game)#Rules).
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "", from = "serde_state::Fields<N, T>")
+)]
 pub struct State<const N: usize, T: StateSpace<N>> {
     /// Current turn
-    pub i: usize,
+    i: usize,
 
     /// `Player` state
-    pub players: [player::Player<N, T>; N],
+    #[cfg_attr(feature = "serde", serde(with = "serde_players"))]
+    players: [player::Player<N, T>; N],
+
+    /// `StateSpace::serialize_state` kept up to date by actions once `cache_serial` is called
+    #[cfg_attr(feature = "serde", serde(skip))]
+    running_serial: Option<u32>,
+
+    /// Number of players not eliminated, kept up to date by actions so `get_status` is O(1)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    alive: usize,
+}
+
+/// `State` is deserialized from its public fields so the players left are counted
+#[cfg(feature = "serde")]
+mod serde_state {
+    use super::{player::Player, State, StateSpace};
+
+    #[derive(serde::Deserialize)]
+    #[serde(bound = "")]
+    pub struct Fields<const N: usize, T: StateSpace<N>> {
+        i: usize,
+        #[serde(with = "super::serde_players")]
+        players: [Player<N, T>; N],
+    }
+
+    impl<const N: usize, T: StateSpace<N>> From<Fields<N, T>> for State<N, T> {
+        fn from(fields: Fields<N, T>) -> State<N, T> {
+            State::from_players(fields.i, fields.players)
+        }
+    }
+}
+
+/// Serde only implements arrays of specific lengths so `players` is a sequence of length `N`
+#[cfg(feature = "serde")]
+mod serde_players {
+    use super::{player::Player, StateSpace};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize, T: StateSpace<N>>(
+        players: &[Player<N, T>; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(players)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize, T: StateSpace<N>>(
+        deserializer: D,
+    ) -> Result<[Player<N, T>; N], D::Error> {
+        let players = Vec::<Player<N, T>>::deserialize(deserializer)?;
+        let len = players.len();
+        players
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &"N players"))
+    }
+}
+
+/// Serde only implements arrays of specific lengths so hands are a sequence of length `LEN`
+#[cfg(feature = "serde")]
+pub(crate) mod serde_hands {
+    use super::Hands;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, H: Hands>(hands: &H, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(hands.as_ref())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, H: Hands>(
+        deserializer: D,
+    ) -> Result<H, D::Error> {
+        let fingers = Vec::<u32>::deserialize(deserializer)?;
+        if fingers.len() != H::LEN {
+            return Err(D::Error::invalid_length(fingers.len(), &"N_HANDS hands"));
+        }
+        let mut hands = H::default();
+        hands.as_mut().copy_from_slice(&fingers);
+        Ok(hands)
+    }
+}
+
+/// A value that does not describe a valid `State`
+#[derive(Debug)]
+pub enum ValueError {
+    SerialOutOfBounds,
+    PlayerIndexOutOfBounds,
+    FingerValueOutOfBounds,
+    SerialOverflow,
+}
+
+/// An abbreviation that does not describe a valid `State`
+#[derive(Debug)]
+pub enum ParseError {
+    InvalidLength,
+    InvalidCharacter,
+    InvalidFingerValue,
 }
 
 impl<const N: usize, T: StateSpace<N> + std::fmt::Debug> Default for State<N, T> {
     fn default() -> Self {
-        State {
-            i: 0,
-            players: (0..N)
-                .map(|_| player::Player::default())
-                .collect::<Vec<_>>()
-                .try_into()
-                .expect("n players"),
+        assert!(T::STARTING_PLAYER < N, "starting player is a player");
+        let players = (0..N)
+            .map(|_| player::Player::default())
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("n players");
+        State::from_players(T::STARTING_PLAYER, players)
+    }
+}
+
+/// Compares the turn and every player's fingers in order without requiring `T: PartialEq`
+impl<const N: usize, T: StateSpace<N>> PartialEq for State<N, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.i == other.i
+            && self
+                .players
+                .iter()
+                .zip(other.players.iter())
+                .all(|(player, other)| player.hands == other.hands)
+    }
+}
+
+impl<const N: usize, T: StateSpace<N>> Eq for State<N, T> {}
+
+/// Hashes the turn and every player's fingers in order, the same fields `PartialEq` compares,
+/// so equal states hash equally. Hand order matters, hash `canonical_serial` to ignore it.
+impl<const N: usize, T: StateSpace<N>> std::hash::Hash for State<N, T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.i.hash(state);
+        for player in self.players.iter() {
+            player.hands.as_ref().hash(state);
+        }
+    }
+}
+
+/// Each player's hands on its own line with the player to move marked, such as `P0: [1,1]  <- turn`
+impl<const N: usize, T: StateSpace<N>> std::fmt::Display for State<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let turn = match self.get_status() {
+            status::Status::Turn { i } => Some(i),
+            status::Status::Over { i: _ } => None,
+        };
+        for (j, player) in self.players.iter().enumerate() {
+            if j > 0 {
+                writeln!(f)?;
+            }
+            let hands = player.hands.as_ref().iter().map(|hand| hand.to_string());
+            write!(f, "P{j}: [{}]", hands.collect::<Vec<_>>().join(","))?;
+            if turn == Some(j) {
+                write!(f, "  <- turn")?;
+            }
         }
+        Ok(())
     }
 }
 
 /// Current state in a game of chopsticks.
 impl<const N: usize, T: StateSpace<N>> State<N, T> {
+    /// A `State` of `players` where it is player `i`'s turn, counting the players left
+    pub(crate) fn from_players(i: usize, players: [player::Player<N, T>; N]) -> State<N, T> {
+        let alive = players
+            .iter()
+            .filter(|player| !player.is_eliminated())
+            .count();
+        State {
+            i,
+            players,
+            running_serial: None,
+            alive,
+        }
+    }
+
+    /// A `State` with each player's `hands` where it is player `i`'s turn
+    pub fn with_hands(hands: [T::Hands; N], i: usize) -> Result<State<N, T>, ValueError> {
+        if i >= N {
+            return Err(ValueError::PlayerIndexOutOfBounds);
+        }
+        if hands
+            .iter()
+            .any(|hands| !is_within_hand_rollovers::<N, T>(hands.as_ref()))
+        {
+            return Err(ValueError::FingerValueOutOfBounds);
+        }
+        let players = hands.map(|hands| {
+            let mut player = player::Player::<N, T>::default();
+            player.hands = hands;
+            player
+        });
+        Ok(State::from_players(i, players))
+    }
+
+    /// The same `State` where it is player `i`'s turn, for choosing who moves first
+    pub fn with_starting_player(mut self, i: usize) -> Result<State<N, T>, ValueError> {
+        if i >= N {
+            return Err(ValueError::PlayerIndexOutOfBounds);
+        }
+        self.set_turn(i);
+        Ok(self)
+    }
+
     /// Player `i` uses hand `a` to attack player `j` at hand `b`.
     pub fn play_attack(
         &mut self,
@@ -40,22 +223,64 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
         j: usize,
         a: usize,
         b: usize,
+    ) -> Result<(), action::AttackError> {
+        self.check_attack(i, j, a, b)?;
+        let attacker = self.players[i].hands[a];
+        let mut hands = self.players[j].hands;
+        hands[b] = T::resolve_attack(hands[b], attacker, b);
+        self.set_hands(j, hands);
+        self.play_iterate_turn();
+        Ok(())
+    }
+
+    /// Player `i` uses their first hand with `a_value` fingers to attack the first other hand of
+    /// player `j` with `b_value` fingers.
+    pub fn play_attack_by_value(
+        &mut self,
+        i: usize,
+        j: usize,
+        a_value: u32,
+        b_value: u32,
+    ) -> Result<(), action::AttackError> {
+        if i >= self.players.len() || j >= self.players.len() {
+            return Err(action::AttackError::PlayerIndexOutOfBounds);
+        }
+        let find = |player: usize, value: u32, skip: Option<usize>| {
+            self.players[player]
+                .hands
+                .as_ref()
+                .iter()
+                .enumerate()
+                .position(|(h, &hand)| hand == value && Some(h) != skip)
+                .ok_or(action::AttackError::HandValueNotFound)
+        };
+        let a = find(i, a_value, None)?;
+        let b = find(j, b_value, (i == j).then_some(a))?;
+        self.play_attack(i, j, a, b)
+    }
+
+    /// Errors if player `i` cannot use hand `a` to attack player `j` at hand `b`
+    fn check_attack(
+        &self,
+        i: usize,
+        j: usize,
+        a: usize,
+        b: usize,
     ) -> Result<(), action::AttackError> {
         if i >= self.players.len() || j >= self.players.len() {
             Err(action::AttackError::PlayerIndexOutOfBounds)
-        } else if a >= N_HANDS || b >= N_HANDS {
+        } else if a >= T::N_HANDS || b >= T::N_HANDS {
             Err(action::AttackError::HandIndexOutOfBounds)
-        } else if i == j {
+        } else if i == j && (!T::ALLOW_SELF_ATTACK || a == b) {
             Err(action::AttackError::PlayerAttackSelf)
         } else {
             let attacker = self.players[i].hands[a];
-            let defending_player = &mut self.players[j];
-            let defender = &mut defending_player.hands[b];
-            if attacker == 0 || *defender == 0 {
+            let defender = self.players[j].hands[b];
+            if attacker == 0 || defender == 0 || self.players[j].is_eliminated() {
                 Err(action::AttackError::HandIsNotAlive)
+            } else if !is_within_rollover::<N, T>(attacker, defender, b) {
+                Err(action::AttackError::ExceedsRollover)
             } else {
-                *defender = (*defender + attacker) % T::ROLLOVER;
-                self.play_iterate_turn();
                 Ok(())
             }
         }
@@ -71,37 +296,95 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
     ) -> Result<(), action::AttackError> {
         if i >= self.players.len() || j >= self.players.len() {
             Err(action::AttackError::PlayerIndexOutOfBounds)
-        } else if a >= N_HANDS || b >= N_HANDS {
+        } else if a >= T::N_HANDS || b >= T::N_HANDS {
             Err(action::AttackError::HandIndexOutOfBounds)
-        } else if i == j {
+        } else if i == j && (!T::ALLOW_SELF_ATTACK || a == b) {
             Err(action::AttackError::PlayerAttackSelf)
         } else {
             let attacker = self.players[i].hands[a];
-            let defending_player = &mut self.players[j];
-            let defender = &mut defending_player.hands[b];
-            let updated_defender = (*defender + (T::ROLLOVER - attacker)) % T::ROLLOVER;
-            if updated_defender == 0 {
-                Err(action::AttackError::HandIsNotAlive)
-            } else {
-                *defender = updated_defender;
-                self.undo_iterate_turn();
+            let mut hands = self.players[j].hands;
+            if let Some(fingers) = T::unresolve_attack(hands[b], attacker, b) {
+                hands[b] = fingers;
+                self.set_hands(j, hands);
+                self.undo_iterate_turn(i);
                 Ok(())
+            } else {
+                Err(action::AttackError::HandIsNotAlive)
             }
         }
     }
 
     /// All possible attack actions from the current `GameState`
     pub fn iter_attack_actions(&self) -> impl Iterator<Item = action::Action<N, T>> + '_ {
+        self.iter_attack_actions_for(self.i)
+    }
+
+    /// Attack actions of player `i` as if it were their turn
+    pub fn iter_attack_actions_for(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = action::Action<N, T>> + '_ {
         self.players
             .iter()
             .enumerate()
-            .filter(|(j, _)| self.i != *j)
+            .filter(move |(j, defender)| {
+                (T::ALLOW_SELF_ATTACK || i != *j) && !defender.is_eliminated()
+            })
             .flat_map(move |(j, defender)| {
-                let a_indexes = self.players[self.i].iter_alive_fingers_indexes();
+                let attacker = &self.players[i];
+                let a_indexes = attacker.iter_alive_fingers_indexes();
                 let b_indexes = defender.iter_alive_fingers_indexes();
                 a_indexes
                     .cartesian_product(b_indexes)
-                    .map(move |(a, b)| action::Action::Attack { i: self.i, j, a, b })
+                    .filter(move |&(a, b)| i != j || a != b)
+                    .filter(move |&(a, b)| {
+                        is_within_rollover::<N, T>(attacker.hands[a], defender.hands[b], b)
+                    })
+                    .map(move |(a, b)| action::Action::Attack { i, j, a, b })
+            })
+    }
+
+    /// The first legal action from this `State` that results in `next`, if any
+    pub fn infer_action(&self, next: &State<N, T>) -> Option<action::Action<N, T>> {
+        let mut state = self.clone();
+        let actions: Vec<_> = self
+            .iter_actions()
+            .filter(|action| self.is_legal(action))
+            .collect();
+        actions.into_iter().find(|action| {
+            state.play_action(action).expect("valid action");
+            let is_next = state == *next;
+            state.undo_action(action).expect("valid action");
+            is_next
+        })
+    }
+
+    /// Actions after which the current player is the only one left standing, none once the game
+    /// is over. These win unless the `StateSpace` is misère.
+    pub fn iter_winning_actions(&self) -> impl Iterator<Item = action::Action<N, T>> + '_ {
+        let mut state = self.clone();
+        let actions: Vec<_> = self
+            .iter_actions()
+            .filter(|action| self.is_legal(action))
+            .filter(|action| {
+                state.play_action(action).expect("valid action");
+                let is_win = state.iter_player_indexes().eq(std::iter::once(self.i));
+                state.undo_action(action).expect("valid action");
+                is_win
+            })
+            .collect();
+        actions.into_iter()
+    }
+
+    /// Attack actions after which the defending hand is dead
+    pub fn iter_killing_attacks(&self) -> impl Iterator<Item = action::Action<N, T>> + '_ {
+        self.iter_attack_actions()
+            .filter(move |action| match *action {
+                action::Action::Attack { i, j, a, b } => {
+                    let attacker = self.players[i].hands[a];
+                    T::resolve_attack(self.players[j].hands[b], attacker, b) == 0
+                }
+                _ => false,
             })
     }
 
@@ -109,20 +392,56 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
     pub fn play_split(
         &mut self,
         i: usize,
-        hands_0: [u32; N_HANDS],
-        hands_1: [u32; N_HANDS],
+        hands_0: T::Hands,
+        hands_1: T::Hands,
+    ) -> Result<(), action::SplitError> {
+        self.check_split(i, hands_0, hands_1)?;
+        self.set_hands(i, hands_1);
+        self.play_iterate_turn();
+        Ok(())
+    }
+
+    /// Player `i` splits their current hands into `hands_1` given as a slice which must have
+    /// `N_HANDS` hands.
+    pub fn play_split_slice(
+        &mut self,
+        i: usize,
+        hands_1: &[u32],
     ) -> Result<(), action::SplitError> {
-        if hands_0 != self.players[i].hands {
+        if hands_1.len() != T::N_HANDS {
+            return Err(action::SplitError::InvalidHandLen);
+        }
+        let hands_0 = self
+            .players
+            .get(i)
+            .ok_or(action::SplitError::ImproperContext)?
+            .hands;
+        let mut hands = T::Hands::default();
+        hands.as_mut().copy_from_slice(hands_1);
+        self.play_split(i, hands_0, hands)
+    }
+
+    /// Errors if player `i` cannot split `hands_0` into `hands_1`
+    fn check_split(
+        &self,
+        i: usize,
+        hands_0: T::Hands,
+        hands_1: T::Hands,
+    ) -> Result<(), action::SplitError> {
+        if self.players.get(i).map(|player| player.hands) != Some(hands_0) {
             Err(action::SplitError::ImproperContext)
-        } else if hands_0.iter().sorted().eq(&hands_1.iter().sorted()) {
+        } else if is_same_hands::<N, T>(hands_0.as_ref(), hands_1.as_ref()) {
             Err(action::SplitError::MoveWithoutChange)
-        } else if hands_0.iter().sum::<u32>() != hands_1.iter().sum() {
+        } else if hands_0.as_ref().iter().sum::<u32>() != hands_1.as_ref().iter().sum::<u32>() {
             Err(action::SplitError::InvalidTotalFingers)
-        } else if hands_1.iter().any(|hand| !(1..T::ROLLOVER).contains(hand)) {
+        } else if hands_1
+            .as_ref()
+            .iter()
+            .enumerate()
+            .any(|(h, hand)| !(min_split_fingers::<N, T>()..T::get_rollover(h)).contains(hand))
+        {
             Err(action::SplitError::InvalidFingerValue)
         } else {
-            self.players[i].hands = hands_1;
-            self.play_iterate_turn();
             Ok(())
         }
     }
@@ -131,41 +450,62 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
     pub fn undo_split(
         &mut self,
         i: usize,
-        hands_0: [u32; N_HANDS],
-        hands_1: [u32; N_HANDS],
+        hands_0: T::Hands,
+        hands_1: T::Hands,
     ) -> Result<(), action::SplitError> {
         if hands_1 != self.players[i].hands {
             Err(action::SplitError::ImproperContext)
-        } else if hands_0.iter().sorted().eq(&hands_1.iter().sorted()) {
+        } else if is_same_hands::<N, T>(hands_0.as_ref(), hands_1.as_ref()) {
             Err(action::SplitError::MoveWithoutChange)
-        } else if hands_0.iter().sum::<u32>() != hands_1.iter().sum() {
+        } else if hands_0.as_ref().iter().sum::<u32>() != hands_1.as_ref().iter().sum::<u32>() {
             Err(action::SplitError::InvalidTotalFingers)
-        } else if hands_0.iter().any(|hand| !(1..T::ROLLOVER).contains(hand)) {
+        } else if !is_within_hand_rollovers::<N, T>(hands_0.as_ref()) {
+            // Hands before a division may be dead so only the upper bound applies
             Err(action::SplitError::InvalidFingerValue)
         } else {
-            self.players[i].hands = hands_0;
-            self.undo_iterate_turn();
+            self.set_hands(i, hands_0);
+            self.undo_iterate_turn(i);
             Ok(())
         }
     }
 
     /// All possible split actions from the current `GameState`
     pub fn iter_split_actions(&self) -> impl Iterator<Item = action::Action<N, T>> + '_ {
-        let total: u32 = self.players[self.i].hands.iter().sum();
-        let start = (total % T::ROLLOVER + 1).max(1);
-        let stop = total / 2;
-        (start..=stop)
-            .map(move |a| -> [u32; N_HANDS] { [a, total - a] })
-            .filter(|&hands| {
-                !self.players[self.i]
-                    .hands
-                    .iter()
-                    .sorted()
-                    .eq(&hands.iter().sorted())
-            })
-            .map(|hands_1| action::Action::Split {
-                i: self.i,
-                hands_0: self.players[self.i].hands,
+        self.iter_split_actions_for(self.i)
+    }
+
+    /// Split actions of player `i` as if it were their turn
+    pub fn iter_split_actions_for(
+        &self,
+        i: usize,
+    ) -> impl Iterator<Item = action::Action<N, T>> + '_ {
+        let hands_0 = self.players[i].hands;
+        let total = hands_0.as_ref().iter().sum();
+        let mut splits = Vec::new();
+        if T::ROLLOVERS.is_some() {
+            push_arrangements::<N, T>(
+                &mut T::Hands::default(),
+                0,
+                min_split_fingers::<N, T>(),
+                total,
+                &mut splits,
+            );
+        } else {
+            push_splits(
+                &mut T::Hands::default(),
+                0,
+                min_split_fingers::<N, T>(),
+                total,
+                T::ROLLOVER,
+                &mut splits,
+            );
+        }
+        splits
+            .into_iter()
+            .filter(move |hands_1| !is_same_hands::<N, T>(hands_0.as_ref(), hands_1.as_ref()))
+            .map(move |hands_1| action::Action::Split {
+                i,
+                hands_0,
                 hands_1,
             })
     }
@@ -175,9 +515,8 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
         &mut self,
         action: &action::Action<N, T>,
     ) -> Result<(), action::ActionError> {
+        self.check_turn(action)?;
         match action {
-            _ if self.iter_player_indexes().count() <= 1 => Err(action::ActionError::GameIsOver),
-            _ if action.get_i() != self.i => Err(action::ActionError::WrongTurn),
             action::Action::Attack { i, j, a, b } => self
                 .play_attack(*i, *j, *a, *b)
                 .map_err(action::ActionError::AttackError),
@@ -188,10 +527,158 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
             } => self
                 .play_split(*i, *hands_0, *hands_1)
                 .map_err(action::ActionError::SplitError),
+            action::Action::Pass { i } if !self.can_pass(*i) => {
+                Err(action::ActionError::PassNotAllowed)
+            }
+            action::Action::Pass { .. } => {
+                self.play_iterate_turn();
+                Ok(())
+            }
             _ => panic!("expect not phantom"),
         }
     }
 
+    /// Whether `play_action` would succeed, without playing `action`
+    pub fn is_legal(&self, action: &action::Action<N, T>) -> bool {
+        self.check_turn(action).is_ok()
+            && match action {
+                action::Action::Attack { i, j, a, b } => self.check_attack(*i, *j, *a, *b).is_ok(),
+                action::Action::Split {
+                    i,
+                    hands_0,
+                    hands_1,
+                } => self.check_split(*i, *hands_0, *hands_1).is_ok(),
+                action::Action::Pass { i } => self.can_pass(*i),
+                _ => panic!("expect not phantom"),
+            }
+    }
+
+    /// Errors if the game is over or it is not the acting player's turn
+    fn check_turn(&self, action: &action::Action<N, T>) -> Result<(), action::ActionError> {
+        if self.alive < if T::ALLOW_SOLO { 1 } else { 2 } {
+            Err(action::ActionError::GameIsOver)
+        } else if action.get_i() != self.i {
+            Err(action::ActionError::WrongTurn)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Resolves one attack from each player `k` at `actions[k]` at once, adding up attacks on the
+    /// same hand. Under `RolloverMode::Cutoff` the combined attacks must not exceed the rollover.
+    pub fn play_simultaneous(
+        &mut self,
+        actions: [action::Action<N, T>; N],
+    ) -> Result<(), action::ActionError> {
+        if self.alive < if T::ALLOW_SOLO { 1 } else { 2 } {
+            return Err(action::ActionError::GameIsOver);
+        }
+        let mut attacks = [T::Hands::default(); N];
+        for (k, action) in actions.iter().enumerate() {
+            if self.players[k].is_eliminated() {
+                continue;
+            }
+            let action::Action::Attack { i, j, a, b } = *action else {
+                return Err(action::ActionError::NotSimultaneous);
+            };
+            if i != k {
+                return Err(action::ActionError::WrongTurn);
+            }
+            self.check_attack(i, j, a, b)
+                .map_err(action::ActionError::AttackError)?;
+            attacks[j][b] += self.players[i].hands[a];
+        }
+        let mut players = self.players.clone();
+        for (player, attacks) in players.iter_mut().zip(attacks) {
+            for (b, hand) in player.hands.as_mut().iter_mut().enumerate() {
+                let attacker = attacks[b];
+                if attacker == 0 {
+                    continue;
+                }
+                if !is_within_rollover::<N, T>(attacker, *hand, b) {
+                    return Err(action::ActionError::AttackError(
+                        action::AttackError::ExceedsRollover,
+                    ));
+                }
+                *hand = T::resolve_attack(*hand, attacker, b);
+            }
+        }
+        if !T::ALLOW_SOLO && players.iter().all(|player| player.is_eliminated()) {
+            return Err(action::ActionError::NotSimultaneous);
+        }
+        for (j, player) in players.iter().enumerate() {
+            self.set_hands(j, player.hands);
+        }
+        if self.players[self.i].is_eliminated() {
+            self.play_iterate_turn();
+        }
+        Ok(())
+    }
+
+    /// Parses an action of the current player in the short notation `a <j> <a> <b>` to attack
+    /// player `j`, `s <hands_1>` to split the current hands into `hands_1` or `p` to pass, such
+    /// as `a 1 0 1` or `s 2 2`. The full notation of `Action`'s `FromStr` is accepted as well.
+    pub fn parse_action(&self, s: &str) -> Result<action::Action<N, T>, action::ParseActionError> {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        let i = self.i;
+        let full = match tokens.as_slice() {
+            ["a", rest @ ..] if rest.len() == 3 => format!("a {i} {}", rest.join(" ")),
+            ["s", rest @ ..] if rest.len() == T::N_HANDS => {
+                let hands_0 = self.players[i].hands;
+                let hands_0: Vec<_> = hands_0.as_ref().iter().map(|h| h.to_string()).collect();
+                format!("s {i} {} {}", hands_0.join(" "), rest.join(" "))
+            }
+            ["p"] => format!("p {i}"),
+            _ => return s.parse(),
+        };
+        full.parse()
+    }
+
+    /// Plays `actions` in order or, on the first invalid one, undoes those already played and
+    /// errors with its index so the `State` is unchanged
+    pub fn play_actions(
+        &mut self,
+        actions: &[action::Action<N, T>],
+    ) -> Result<(), (usize, action::ActionError)> {
+        for (k, action) in actions.iter().enumerate() {
+            if let Err(error) = self.play_action(action) {
+                for played in actions[..k].iter().rev() {
+                    self.undo_action(played).expect("valid action");
+                }
+                return Err((k, error));
+            }
+        }
+        Ok(())
+    }
+
+    /// `play_action` that also returns the turn and hands it overwrote, for undo stacks that
+    /// should not depend on inverting actions
+    pub fn play_action_reversible(
+        &mut self,
+        action: &action::Action<N, T>,
+    ) -> Result<action::ReversibleToken<N, T>, action::ActionError> {
+        let i = self.i;
+        let player = match *action {
+            action::Action::Attack { j, .. } => j,
+            action::Action::Split { i, .. } | action::Action::Pass { i } => i,
+            action::Action::Phantom(_) => panic!("expect not phantom"),
+        };
+        let hands = self.players.get(player).map(|player| player.hands);
+        self.play_action(action)?;
+        Ok(action::ReversibleToken {
+            i,
+            player,
+            hands: hands.expect("valid action"),
+        })
+    }
+
+    /// Restores the `State` from before the action that returned `token`. Tokens must be undone
+    /// in the reverse order they were played.
+    pub fn undo_with(&mut self, token: action::ReversibleToken<N, T>) {
+        self.set_hands(token.player, token.hands);
+        self.set_turn(token.i);
+    }
+
     pub fn undo_action(
         &mut self,
         action: &action::Action<N, T>,
@@ -207,171 +694,1353 @@ impl<const N: usize, T: StateSpace<N>> State<N, T> {
             } => self
                 .undo_split(*i, *hands_0, *hands_1)
                 .map_err(action::ActionError::SplitError),
+            action::Action::Pass { .. } if !T::HAS_PASS => Err(action::ActionError::PassNotAllowed),
+            action::Action::Pass { i } if *i >= N => Err(action::ActionError::WrongTurn),
+            action::Action::Pass { i } => {
+                self.undo_iterate_turn(*i);
+                Ok(())
+            }
             _ => panic!("expect not phantom"),
         }
     }
 
     /// All potential actions
     pub fn iter_actions(&self) -> impl Iterator<Item = action::Action<N, T>> + '_ {
-        self.iter_attack_actions().chain(self.iter_split_actions())
+        self.iter_actions_for(self.i)
     }
 
-    /// Updates `i` to indicate the next *player's* turn
-    fn play_iterate_turn(&mut self) {
-        if matches!(self.get_status(), status::Status::Turn { .. }) {
-            self.i = self
-                .players
-                .iter()
-                .enumerate()
-                .filter(|(_, player)| !player.is_eliminated())
-                .cycle()
-                .nth(self.i + 1)
-                .expect("multiple players")
-                .0;
-        }
+    /// Actions of player `i` as if it were their turn, without changing the current turn.
+    /// Panics if `i` is not a player.
+    pub fn iter_actions_for(&self, i: usize) -> impl Iterator<Item = action::Action<N, T>> + '_ {
+        self.iter_attack_actions_for(i)
+            .chain(self.iter_split_actions_for(i))
+            .chain(self.can_pass(i).then_some(action::Action::Pass { i }))
     }
 
-    /// Updates `i` to indicate the previous player's turn
-    fn undo_iterate_turn(&mut self) {
-        if matches!(self.get_status(), status::Status::Turn { .. }) {
-            self.i = self
-                .players
-                .iter()
-                .enumerate()
-                .filter(|(_, player)| !player.is_eliminated())
-                .rev()
-                .cycle()
-                .nth(T::N_PLAYERS - self.i)
-                .expect("multiple players")
-                .0;
-        }
+    /// A pass for the current player if `ALLOW_PASS` or forced
+    pub fn get_pass_action(&self) -> Option<action::Action<N, T>> {
+        self.can_pass(self.i)
+            .then_some(action::Action::Pass { i: self.i })
     }
 
-    /// The 'abbreviation' representation of the game state.
-    pub fn get_abbreviation(&self) -> String {
-        self.players
-            .iter()
-            .flat_map(|player| player.hands.iter().map(|hand| hand.to_string()))
-            .collect()
+    /// Whether player `i` may pass by `ALLOW_PASS` or must under `RolloverMode::Cutoff` since
+    /// they have no attack or split
+    fn can_pass(&self, i: usize) -> bool {
+        T::ALLOW_PASS
+            || T::HAS_PASS
+                && self.iter_attack_actions_for(i).next().is_none()
+                && self.iter_split_actions_for(i).next().is_none()
     }
 
-    /// Current game stage panics with no players
-    pub fn get_status(&self) -> status::Status {
-        let i = self.i;
-        match self.iter_player_indexes().count() {
-            0 => panic!("no non-eliminated players"),
-            1 => status::Status::Over { i },
-            _ => status::Status::Turn { i },
+    /// All potential actions collected by kind
+    pub fn legal_actions(&self) -> action::LegalActions<N, T> {
+        action::LegalActions {
+            attacks: self.iter_attack_actions().collect(),
+            splits: self.iter_split_actions().collect(),
+            pass: self.get_pass_action(),
         }
     }
 
-    /// Detects loop state for 2 player with rollover 5
-    pub fn is_loop_state(&self) -> bool {
-        // Could this be done another way?
-        if T::N_PLAYERS != 2 || T::INITIAL_FINGERS != 1 || T::ROLLOVER != 5 {
-            panic!("not implemented for the `SpaceState`");
+    /// Number of potential actions without collecting them
+    pub fn count_actions(&self) -> usize {
+        let hands = self.players[self.i].hands;
+        let total = hands.as_ref().iter().sum();
+        let min = min_split_fingers::<N, T>();
+        let splits = count_split_suffix::<N, T>(total, 0, min);
+        // The current hands are among the arrangements when every hand may be split to
+        let current = hands.as_ref().iter().all(|&hand| hand >= min) as usize;
+        let pass = self.get_pass_action().is_some() as usize;
+        self.iter_attack_actions().count() + splits - current + pass
+    }
+
+    /// Number of legal actions, equal to `count_actions` while the game is ongoing and 0 once it
+    /// is over
+    pub fn branching_factor(&self) -> usize {
+        if self.is_terminal() {
+            0
+        } else {
+            self.count_actions()
         }
-        self.players[0].hands.iter().sorted().eq(&[&0, &1])
-            && self.players[1].hands.iter().sorted().eq(&[&0, &2])
-            || self.players[0].hands.iter().sorted().eq(&[&0, &2])
-                && self.players[1].hands.iter().sorted().eq(&[&0, &1])
     }
 
-    /// Iterate non eliminated player indexes
-    pub fn iter_player_indexes(&self) -> impl Iterator<Item = usize> + '_ {
-        self.players
+    /// The `n`th action of `iter_actions` without generating the splits before it
+    pub fn nth_action(&self, n: usize) -> Option<action::Action<N, T>> {
+        let attacks = self.iter_attack_actions().count();
+        if n < attacks {
+            return self.iter_attack_actions().nth(n);
+        }
+        let hands_0 = self.players[self.i].hands;
+        let total = hands_0.as_ref().iter().sum();
+        let min = min_split_fingers::<N, T>();
+        let splits = count_split_suffix::<N, T>(total, 0, min);
+        // The arrangement of the current hands is not a split so later splits are one further
+        let mut current = hands_0;
+        if T::ROLLOVERS.is_none() {
+            current.as_mut().sort_unstable();
+        }
+        let skipped = current
+            .as_ref()
             .iter()
-            .enumerate()
-            .filter(|(_, player)| !player.is_eliminated())
-            .map(|(i, _)| i)
+            .all(|&hand| hand >= min)
+            .then(|| get_split_rank::<N, T>(current.as_ref()));
+        let k = match skipped {
+            Some(rank) if n - attacks >= rank => n - attacks + 1,
+            _ => n - attacks,
+        };
+        if k < splits {
+            let hands_1 = get_ranked_split::<N, T>(total, k).expect("split within count");
+            Some(action::Action::Split {
+                i: self.i,
+                hands_0,
+                hands_1,
+            })
+        } else if k == splits {
+            self.get_pass_action()
+        } else {
+            None
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::state_space::chopsticks::Chopsticks;
 
-    #[test]
-    fn two_players() {
-        assert_eq!(
-            Chopsticks.get_initial_state(),
-            State {
-                i: 0,
-                players: [player::Player::default(), player::Player::default()],
+    /// Plays uniformly random actions until the game is over or a `State` occurs
+    /// `game::MAX_REPETITIONS` times, then undoes them. Returns the last player standing or
+    /// `None` for a draw by repetition.
+    pub fn simulate_random<R: rand::Rng>(&mut self, rng: &mut R) -> Option<usize> {
+        #[cfg(debug_assertions)]
+        let before = (self.i, self.get_abbreviation());
+        let mut history = Vec::new();
+        let mut repetitions = HashMap::from([(T::serialize_state(self), 1)]);
+        let last = loop {
+            if let status::Status::Over { i } = self.get_status() {
+                break Some(i);
             }
-        );
+            let n = rng.gen_range(0..self.count_actions());
+            let action = self.nth_action(n).expect("multiple actions");
+            self.play_action(&action).expect("valid action");
+            history.push(action);
+            let count = repetitions.entry(T::serialize_state(self)).or_insert(0);
+            *count += 1;
+            if *count >= crate::game::MAX_REPETITIONS {
+                break None;
+            }
+        };
+        while let Some(action) = history.pop() {
+            self.undo_action(&action).expect("valid action");
+        }
+        #[cfg(debug_assertions)]
+        assert_eq!((self.i, self.get_abbreviation()), before);
+        last
     }
 
-    #[test]
-    fn attack_invalid_index() {
-        let mut game_state = Chopsticks.get_initial_state();
-        assert!(game_state.play_attack(0, 0, 0, 0).is_err());
-        assert!(game_state.play_attack(0, 1, 2, 0).is_err());
-        assert!(game_state.play_attack(0, 1, 0, 2).is_err());
-        assert!(game_state.play_attack(0, 2, 0, 0).is_err());
+    /// Updates `i` to indicate the next *player's* turn
+    fn play_iterate_turn(&mut self) {
+        if matches!(self.get_status(), status::Status::Turn { .. }) {
+            let i = (1..=N)
+                .map(|k| {
+                    if T::CLOCKWISE {
+                        (self.i + k) % N
+                    } else {
+                        (self.i + N - k) % N
+                    }
+                })
+                .find(|&j| !self.players[j].is_eliminated())
+                .expect("multiple players");
+            self.set_turn(i);
+        }
     }
 
-    #[test]
-    fn attacker_is_zero() {
-        let mut game_state = Chopsticks.get_initial_state();
-        game_state.players[0].hands[0] = 0;
-        assert!(game_state.play_attack(0, 1, 0, 0).is_err());
+    /// Updates `i` to the player `i` who made the undone action
+    fn undo_iterate_turn(&mut self, i: usize) {
+        self.set_turn(i);
     }
 
-    #[test]
-    fn defender_is_zero() {
-        let mut game_state = Chopsticks.get_initial_state();
-        game_state.players[1].hands[0] = 0;
-        assert!(game_state.play_attack(0, 1, 0, 0).is_err());
+    /// Keeps `running_serial` up to date through actions from now on
+    pub fn cache_serial(&mut self) {
+        self.running_serial = Some(T::serialize_state(self));
     }
 
-    #[test]
-    fn attack_with_one() {
-        let mut game_state = Chopsticks.get_initial_state();
-        assert!(game_state.play_attack(0, 1, 0, 0).is_ok());
-        assert_eq!(game_state.i, 1);
-        assert_eq!(game_state.players[1].hands[0], 2);
+    /// `StateSpace::serialize_state` without recomputing it after `cache_serial`
+    pub fn cached_serial(&self) -> u32 {
+        match self.running_serial {
+            Some(serial) => {
+                debug_assert_eq!(serial, T::serialize_state(self), "stale serial");
+                serial
+            }
+            None => T::serialize_state(self),
+        }
     }
 
-    #[test]
-    fn attack_with_four() {
-        let mut game_state = Chopsticks.get_initial_state();
-        game_state.players[0].hands[1] = 4;
-        assert!(game_state.play_attack(0, 1, 1, 1).is_ok());
-        assert_eq!(game_state.i, 1);
-        assert_eq!(game_state.players[1].hands[1], 0);
+    /// Sets the `hands` of `player` and updates the running serial by their digits and the
+    /// number of players left
+    fn set_hands(&mut self, player: usize, hands: T::Hands) {
+        if let Some(serial) = self.running_serial {
+            let weight = T::ROLLOVER.pow((player * T::N_HANDS) as u32) * T::N_PLAYERS as u32;
+            let digits = |hands: &T::Hands| {
+                let hands = hands.as_ref().iter().rev();
+                hands.fold(0, |digits, hand| digits * T::ROLLOVER + hand)
+            };
+            let old = digits(&self.players[player].hands);
+            self.running_serial = Some(serial - old * weight + digits(&hands) * weight);
+        }
+        let was_eliminated = self.players[player].is_eliminated();
+        self.players[player].hands = hands;
+        match (was_eliminated, self.players[player].is_eliminated()) {
+            (false, true) => self.alive -= 1,
+            (true, false) => self.alive += 1,
+            _ => {}
+        }
     }
 
-    #[test]
-    fn split_with_zero() {
-        let mut game_state = Chopsticks.get_initial_state();
-        assert!(game_state.play_split(0, [1, 1], [0, 2]).is_err());
-        assert!(game_state.play_split(0, [1, 1], [2, 0]).is_err());
+    /// Sets the turn to `i` and updates the running serial by its digit
+    fn set_turn(&mut self, i: usize) {
+        if let Some(serial) = self.running_serial {
+            self.running_serial = Some(serial - self.i as u32 + i as u32);
+        }
+        self.i = i;
     }
 
-    #[test]
-    fn split_with_five() {
-        let mut game_state = Chopsticks.get_initial_state();
-        game_state.players[0].hands = [4; 2];
-        assert!(game_state.play_split(0, [4, 4], [5, 3]).is_err());
-        assert!(game_state.play_split(0, [4, 4], [3, 5]).is_err());
+    /// Each player's total fingers less the average total of the other players, rounded toward
+    /// zero, as a baseline evaluation for any number of players
+    pub fn material_scores(&self) -> [i32; N] {
+        let totals = self
+            .players
+            .each_ref()
+            .map(|player| player.total_fingers() as i32);
+        let sum: i32 = totals.iter().sum();
+        totals.map(|total| match N {
+            1 => total,
+            _ => total - (sum - total) / (N as i32 - 1),
+        })
     }
 
-    #[test]
+    /// Estimate in `-1.0..=1.0` for player `i` by a 2-ply search over halved `material_scores`
+    pub fn advantage(&self, i: usize) -> f64 {
+        self.clone().get_advantage(i, 2)
+    }
+
+    /// `advantage` of player `i` searched `depth` plies deep
+    fn get_advantage(&mut self, i: usize, depth: usize) -> f64 {
+        if self.is_terminal() {
+            return match self.get_winner() {
+                Some(winner) if winner == i => 1.0,
+                Some(_) => -1.0,
+                None => 0.0,
+            };
+        }
+        if depth == 0 {
+            let max_fingers: u32 = (0..T::N_HANDS).map(|h| T::get_rollover(h) - 1).sum();
+            let score = self.material_scores()[i] as f64 / max_fingers as f64;
+            return score.clamp(-1.0, 1.0) / 2.0;
+        }
+        let maximize = self.i == i;
+        let actions: Vec<_> = self.iter_actions().collect();
+        let values = actions.iter().map(|action| {
+            self.play_action(action).expect("valid action");
+            let value = self.get_advantage(i, depth - 1);
+            self.undo_action(action).expect("valid action");
+            value
+        });
+        if maximize {
+            values.fold(-1.0, f64::max)
+        } else {
+            values.fold(1.0, f64::min)
+        }
+    }
+
+    /// The 'abbreviation' representation of the game state.
+    pub fn get_abbreviation(&self) -> String {
+        self.players
+            .iter()
+            .flat_map(|player| player.hands.as_ref().iter().map(|hand| hand.to_string()))
+            .collect()
+    }
+
+    /// Parses the 'abbreviation' representation of a game state with one digit per hand. The
+    /// abbreviation does not include the current turn so it is player 0's turn.
+    pub fn from_abbreviation(abbreviation: &str) -> Result<State<N, T>, ParseError> {
+        if abbreviation.chars().count() != N * T::N_HANDS {
+            return Err(ParseError::InvalidLength);
+        }
+        let fingers = abbreviation
+            .chars()
+            .map(|c| c.to_digit(10).ok_or(ParseError::InvalidCharacter))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !fingers
+            .chunks(T::N_HANDS)
+            .all(|hands| is_within_hand_rollovers::<N, T>(hands))
+        {
+            return Err(ParseError::InvalidFingerValue);
+        }
+        let players = std::array::from_fn(|k| {
+            let mut player = player::Player::<N, T>::default();
+            player
+                .hands
+                .as_mut()
+                .copy_from_slice(&fingers[k * T::N_HANDS..(k + 1) * T::N_HANDS]);
+            player
+        });
+        Ok(State::from_players(0, players))
+    }
+
+    /// `StateSpace::serialize_state` as any integer type. Panics if the serial does not fit in
+    /// `U` which `StateSpace::check_serial_size` rules out ahead of time.
+    pub fn serial<U: num_traits::PrimInt>(&self) -> U {
+        U::from(T::serialize_state(self)).expect("serial fits")
+    }
+
+    /// Inverse of `serial` or `None` if `serial` is out of bounds or has a hand beyond its
+    /// rollover
+    pub fn from_serial<U: num_traits::PrimInt>(serial: U) -> Option<State<N, T>> {
+        let state = T::deserialize_state(serial.to_u32()?).ok()?;
+        state
+            .players
+            .iter()
+            .all(|player| is_within_hand_rollovers::<N, T>(player.hands.as_ref()))
+            .then_some(state)
+    }
+
+    /// Bit packs the `State` for cheap keys. The turn takes the least significant
+    /// `bit_width(N - 1)` bits followed by each hand of each player in order, each taking
+    /// `bit_width(ROLLOVER - 1)` bits. Panics if the fields need more than 64 bits.
+    pub fn pack(&self) -> u64 {
+        let (turn_bits, hand_bits) = get_packed_widths::<N, T>();
+        assert!(
+            turn_bits + hand_bits * (N * T::N_HANDS) as u32 <= u64::BITS,
+            "state fits in u64"
+        );
+        self.players
+            .iter()
+            .flat_map(|player| player.hands.as_ref().iter())
+            .rev()
+            .fold(0, |bits, &hand| bits << hand_bits | hand as u64)
+            << turn_bits
+            | self.i as u64
+    }
+
+    /// Inverse of `pack` or `None` if `bits` has a turn or hand out of bounds, bits beyond the
+    /// fields, or the fields need more than 64 bits
+    pub fn unpack(bits: u64) -> Option<State<N, T>> {
+        let (turn_bits, hand_bits) = get_packed_widths::<N, T>();
+        let total_bits = turn_bits + hand_bits * (N * T::N_HANDS) as u32;
+        if total_bits > u64::BITS || bits.checked_shr(total_bits).unwrap_or(0) != 0 {
+            return None;
+        }
+        let i = (bits & get_mask(turn_bits)) as usize;
+        let mut rest = bits.checked_shr(turn_bits).unwrap_or(0);
+        let players = std::array::from_fn(|_| {
+            let mut player = player::Player::<N, T>::default();
+            for hand in player.hands.as_mut().iter_mut() {
+                *hand = (rest & get_mask(hand_bits)) as u32;
+                rest = rest.checked_shr(hand_bits).unwrap_or(0);
+            }
+            player
+        });
+        let state = State::from_players(i, players);
+        let is_in_bounds = state
+            .players
+            .iter()
+            .all(|player| is_within_hand_rollovers::<N, T>(player.hands.as_ref()));
+        (i < N && is_in_bounds).then_some(state)
+    }
+
+    /// Cyclically relabels players so the current player is index 0. Also returns the
+    /// permutation where index `k` of the rotated `State` was index `permutation[k]` originally.
+    pub fn rotate_to_current(&self) -> (State<N, T>, [usize; N]) {
+        let permutation: [usize; N] = std::array::from_fn(|k| (self.i + k) % N);
+        let state = State::from_players(0, permutation.map(|j| self.players[j].clone()));
+        (state, permutation)
+    }
+
+    /// `rotate_to_current` without the permutation
+    pub fn normalize_turn(&self) -> State<N, T> {
+        self.rotate_to_current().0
+    }
+
+    /// Sorts each player's hands ascending unless `ROLLOVERS` makes hands differ
+    pub fn canonicalize(&mut self) {
+        if T::ROLLOVERS.is_some() {
+            return;
+        }
+        for player in 0..N {
+            let mut hands = self.players[player].hands;
+            hands.as_mut().sort_unstable();
+            self.set_hands(player, hands);
+        }
+    }
+
+    /// `serialize_state` of the canonical form so hand order symmetric states share a serial
+    pub fn canonical_serial(&self) -> u32 {
+        let mut state = self.clone();
+        state.canonicalize();
+        T::serialize_state(&state)
+    }
+
+    /// Breadth first traversal of every `State` reachable from this one, including itself, each
+    /// yielded once by `serialize_state`. Finished games are yielded but not expanded.
+    pub fn iter_reachable(&self) -> impl Iterator<Item = State<N, T>> {
+        let mut seen = HashSet::from([T::serialize_state(self)]);
+        let mut queue = VecDeque::from([self.clone()]);
+        std::iter::from_fn(move || {
+            let mut state = queue.pop_front()?;
+            if let status::Status::Turn { .. } = state.get_status() {
+                let actions: Vec<_> = state.iter_actions().collect();
+                for action in actions {
+                    state.play_action(&action).expect("valid action");
+                    if seen.insert(T::serialize_state(&state)) {
+                        queue.push_back(state.clone());
+                    }
+                    state.undo_action(&action).expect("valid action");
+                }
+            }
+            Some(state)
+        })
+    }
+
+    /// Current game stage panics with no players unless `ALLOW_SOLO`
+    pub fn get_status(&self) -> status::Status {
+        let i = self.i;
+        match self.alive {
+            0 if T::ALLOW_SOLO => status::Status::Over { i },
+            0 => panic!("no non-eliminated players"),
+            1 if T::ALLOW_SOLO => status::Status::Turn { i },
+            1 => status::Status::Over { i },
+            _ => status::Status::Turn { i },
+        }
+    }
+
+    /// The winner once the game is over. In misère the winner is the other player of a two player
+    /// game, with more players the first eliminated player wins which the `State` does not record.
+    pub fn get_winner(&self) -> Option<usize> {
+        match self.get_status() {
+            status::Status::Over { i } if !T::MISERE => Some(i),
+            status::Status::Over { i } if N == 2 => Some(1 - i),
+            _ => None,
+        }
+    }
+
+    /// `get_status` as text naming the winner from `get_winner` once the game is over, "Over: P1"
+    /// for the last player standing when the winner is unknown, or the turn while it is ongoing
+    pub fn get_status_text(&self) -> String {
+        match (self.get_status(), self.get_winner()) {
+            (status::Status::Over { .. }, Some(winner)) => format!("Winner: P{winner}"),
+            (status::Status::Over { i }, None) => format!("Over: P{i}"),
+            (status @ status::Status::Turn { .. }, _) => status.to_string(),
+        }
+    }
+
+    /// Whether `get_status` is `Over`
+    pub fn is_terminal(&self) -> bool {
+        matches!(self.get_status(), status::Status::Over { .. })
+    }
+
+    /// Index of the player whose turn it is, only changed through actions like `players`
+    pub fn get_i(&self) -> usize {
+        self.i
+    }
+
+    /// Each `Player` indexed by turn, only changed through actions so the players left stay counted
+    pub fn get_players(&self) -> &[player::Player<N, T>; N] {
+        &self.players
+    }
+
+    /// Whether player `i` is not eliminated or `None` if there is no player `i`
+    pub fn is_player_alive(&self, i: usize) -> Option<bool> {
+        self.players.get(i).map(|player| !player.is_eliminated())
+    }
+
+    /// Iterate non eliminated player indexes
+    pub fn iter_player_indexes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.players
+            .iter()
+            .enumerate()
+            .filter(|(_, player)| !player.is_eliminated())
+            .map(|(i, _)| i)
+    }
+}
+
+/// Whether an `attacker` may attack a `defender` at hand `b` under the `ROLLOVER_MODE`
+fn is_within_rollover<const N: usize, T: StateSpace<N>>(
+    attacker: u32,
+    defender: u32,
+    b: usize,
+) -> bool {
+    match T::ROLLOVER_MODE {
+        RolloverMode::Modulo => true,
+        RolloverMode::Cutoff => attacker + defender <= T::get_fingers(b),
+    }
+}
+
+/// Whether each hand is below its rollover
+fn is_within_hand_rollovers<const N: usize, T: StateSpace<N>>(hands: &[u32]) -> bool {
+    hands
+        .iter()
+        .enumerate()
+        .all(|(h, &hand)| hand < T::get_rollover(h))
+}
+
+/// Bits of the turn and of each hand for `State::pack`
+fn get_packed_widths<const N: usize, T: StateSpace<N>>() -> (u32, u32) {
+    let bit_width = |max: u64| u64::BITS - max.leading_zeros();
+    (bit_width(N as u64 - 1), bit_width(T::ROLLOVER as u64 - 1))
+}
+
+/// The lowest `bits` bits set
+fn get_mask(bits: u32) -> u64 {
+    u64::MAX.checked_shr(u64::BITS - bits).unwrap_or(0)
+}
+
+/// Fewest fingers a hand may hold after a split. Players always have fingers to split so a
+/// revived split leaves at least one hand alive.
+fn min_split_fingers<const N: usize, T: StateSpace<N>>() -> u32 {
+    if T::ALLOW_REVIVE {
+        0
+    } else {
+        1
+    }
+}
+
+/// Whether two hand arrangements hold the same multiset of finger values, or the same finger
+/// values in order when hands have different `ROLLOVERS`. Finger values are always below
+/// `ROLLOVER` so they are compared as is rather than mod `ROLLOVER`.
+fn is_same_hands<const N: usize, T: StateSpace<N>>(hands_0: &[u32], hands_1: &[u32]) -> bool {
+    if T::ROLLOVERS.is_some() {
+        hands_0 == hands_1
+    } else {
+        hands_0.iter().sorted().eq(&hands_1.iter().sorted())
+    }
+}
+
+/// Pushes each ascending arrangement of `hands` from index `h` onward with values in
+/// `min..rollover` summing to `total`
+fn push_splits<H: Hands>(
+    hands: &mut H,
+    h: usize,
+    min: u32,
+    total: u32,
+    rollover: u32,
+    splits: &mut Vec<H>,
+) {
+    if h == H::LEN {
+        if total == 0 {
+            splits.push(*hands);
+        }
+        return;
+    }
+    let remaining = (H::LEN - h) as u32;
+    for hand in (min..rollover).take_while(|&hand| hand * remaining <= total) {
+        hands[h] = hand;
+        push_splits(hands, h + 1, hand, total - hand, rollover, splits);
+    }
+}
+
+/// Pushes every arrangement of `hands` from index `h` onward with each hand in
+/// `min..get_rollover(h)` summing to `total`
+fn push_arrangements<const N: usize, T: StateSpace<N>>(
+    hands: &mut T::Hands,
+    h: usize,
+    min: u32,
+    total: u32,
+    splits: &mut Vec<T::Hands>,
+) {
+    if h == T::N_HANDS {
+        if total == 0 {
+            splits.push(*hands);
+        }
+        return;
+    }
+    for hand in (min..T::get_rollover(h)).take_while(|&hand| hand <= total) {
+        hands[h] = hand;
+        push_arrangements::<N, T>(hands, h + 1, min, total - hand, splits);
+    }
+}
+
+/// Ways to arrange `total` among hands `h..N_HANDS` in the order of `iter_split_actions` when
+/// hand `h - 1` holds `previous` fingers. Hands ascend unless they have `ROLLOVERS`.
+fn count_split_suffix<const N: usize, T: StateSpace<N>>(
+    total: u32,
+    h: usize,
+    previous: u32,
+) -> usize {
+    if T::ROLLOVERS.is_none() {
+        return state_space::count_splits(total, T::N_HANDS - h, previous, T::ROLLOVER);
+    }
+    if h == T::N_HANDS {
+        return (total == 0) as usize;
+    }
+    let min = min_split_fingers::<N, T>();
+    (min..T::get_rollover(h))
+        .take_while(|&hand| hand <= total)
+        .map(|hand| count_split_suffix::<N, T>(total - hand, h + 1, hand))
+        .sum()
+}
+
+/// Lowest fingers hand `h` may hold in a split after a hand with `previous` fingers
+fn get_split_floor<const N: usize, T: StateSpace<N>>(previous: u32) -> u32 {
+    if T::ROLLOVERS.is_none() {
+        previous
+    } else {
+        min_split_fingers::<N, T>()
+    }
+}
+
+/// Index of the arrangement `hands` among every arrangement of its total in the order of
+/// `iter_split_actions`, including the current hands which it skips
+fn get_split_rank<const N: usize, T: StateSpace<N>>(hands: &[u32]) -> usize {
+    let mut total: u32 = hands.iter().sum();
+    let mut previous = min_split_fingers::<N, T>();
+    let mut rank = 0;
+    for (h, &hand) in hands.iter().enumerate() {
+        rank += (get_split_floor::<N, T>(previous)..hand)
+            .take_while(|&lower| lower <= total)
+            .map(|lower| count_split_suffix::<N, T>(total - lower, h + 1, lower))
+            .sum::<usize>();
+        total -= hand;
+        previous = hand;
+    }
+    rank
+}
+
+/// Inverse of `get_split_rank` for arrangements of `total`
+fn get_ranked_split<const N: usize, T: StateSpace<N>>(total: u32, rank: usize) -> Option<T::Hands> {
+    let mut hands = T::Hands::default();
+    let mut total = total;
+    let mut rank = rank;
+    let mut previous = min_split_fingers::<N, T>();
+    for h in 0..T::N_HANDS {
+        let mut lower = get_split_floor::<N, T>(previous);
+        loop {
+            if lower >= T::get_rollover(h) || lower > total {
+                return None;
+            }
+            let count = count_split_suffix::<N, T>(total - lower, h + 1, lower);
+            if rank < count {
+                break;
+            }
+            rank -= count;
+            lower += 1;
+        }
+        hands[h] = lower;
+        total -= lower;
+        previous = lower;
+    }
+    Some(hands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{
+        chopsticks::Chopsticks, cutoff_chopsticks::CutoffChopsticks,
+        misere_chopsticks::MisereChopsticks, three_handed::ThreeHanded,
+    };
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct ThreePlayers;
+
+    impl StateSpace<3> for ThreePlayers {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct CounterClockwise;
+
+    impl StateSpace<3> for CounterClockwise {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const CLOCKWISE: bool = false;
+    }
+
+    #[test]
+    fn counter_clockwise() {
+        let mut game_state = CounterClockwise.get_initial_state();
+        let mut turns = vec![game_state.i];
+        for _ in 0..3 {
+            let action = game_state.iter_attack_actions().next().expect("attack");
+            assert!(game_state.play_action(&action).is_ok());
+            turns.push(game_state.i);
+        }
+        assert_eq!(turns, [0, 2, 1, 0]);
+        let mut game_state =
+            State::<3, CounterClockwise>::with_hands([[1, 1], [1, 1], [0, 0]], 0).expect("valid");
+        let action = game_state.iter_attack_actions().next().expect("attack");
+        assert!(game_state.play_action(&action).is_ok());
+        assert_eq!(game_state.i, 1);
+        assert!(game_state.undo_action(&action).is_ok());
+        assert_eq!(game_state.i, 0);
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Solo;
+
+    impl StateSpace<1> for Solo {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 2;
+        const ALLOW_SOLO: bool = true;
+    }
+
+    #[test]
+    fn solo() {
+        let mut game_state = Solo.get_initial_state();
+        assert_eq!(game_state.iter_attack_actions().count(), 0);
+        for _ in 0..10 {
+            assert!(matches!(
+                game_state.get_status(),
+                status::Status::Turn { i: 0 }
+            ));
+            let action = game_state.iter_actions().next().expect("split action");
+            assert!(matches!(action, action::Action::Split { .. }));
+            assert!(game_state.play_action(&action).is_ok());
+        }
+        let game_state = State::<1, Solo>::from_abbreviation("00").expect("valid");
+        assert!(matches!(
+            game_state.get_status(),
+            status::Status::Over { i: 0 }
+        ));
+    }
+
+    #[test]
+    fn two_players() {
+        assert_eq!(
+            Chopsticks.get_initial_state(),
+            State::from_players(0, [player::Player::default(), player::Player::default()])
+        );
+    }
+
+    #[test]
+    fn attack_invalid_index() {
+        let mut game_state = Chopsticks.get_initial_state();
+        assert!(game_state.play_attack(0, 0, 0, 0).is_err());
+        assert!(game_state.play_attack(0, 1, 2, 0).is_err());
+        assert!(game_state.play_attack(0, 1, 0, 2).is_err());
+        assert!(game_state.play_attack(0, 2, 0, 0).is_err());
+    }
+
+    #[test]
+    fn attacker_is_zero() {
+        let mut game_state = Chopsticks.get_initial_state();
+        game_state.set_hands(0, [0, 1]);
+        assert!(game_state.play_attack(0, 1, 0, 0).is_err());
+    }
+
+    #[test]
+    fn defender_is_zero() {
+        let mut game_state = Chopsticks.get_initial_state();
+        game_state.set_hands(1, [0, 1]);
+        assert!(game_state.play_attack(0, 1, 0, 0).is_err());
+    }
+
+    #[test]
+    fn attack_with_one() {
+        let mut game_state = Chopsticks.get_initial_state();
+        assert!(game_state.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.i, 1);
+        assert_eq!(game_state.players[1].hands[0], 2);
+    }
+
+    #[test]
+    fn attack_with_four() {
+        let mut game_state =
+            State::<2, Chopsticks>::with_hands([[1, 4], [1, 1]], 0).expect("valid");
+        assert!(game_state.play_attack(0, 1, 1, 1).is_ok());
+        assert_eq!(game_state.i, 1);
+        assert_eq!(game_state.players[1].hands[1], 0);
+    }
+
+    #[test]
+    fn attack_overflow_modulo() {
+        let mut game_state =
+            State::<2, Chopsticks>::with_hands([[3, 1], [3, 1]], 0).expect("valid");
+        assert_eq!(game_state.iter_attack_actions().count(), 4);
+        assert!(game_state.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.players[1].hands, [1, 1]);
+    }
+
+    #[test]
+    fn attack_overflow_cutoff() {
+        let mut game_state =
+            State::<2, CutoffChopsticks>::with_hands([[3, 1], [3, 4]], 0).expect("valid");
+        assert_eq!(game_state.iter_attack_actions().count(), 2);
+        assert!(matches!(
+            game_state.play_attack(0, 1, 0, 0),
+            Err(action::AttackError::ExceedsRollover)
+        ));
+        assert_eq!(game_state.players[1].hands, [3, 4]);
+        assert!(game_state.play_attack(0, 1, 1, 1).is_ok());
+        assert_eq!(game_state.players[1].hands, [3, 0]);
+        assert!(game_state.undo_attack(0, 1, 1, 1).is_ok());
+        assert_eq!(game_state.players[1].hands, [3, 4]);
+    }
+
+    #[test]
+    fn is_legal() {
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        for action in game_state.iter_actions() {
+            assert!(game_state.is_legal(&action));
+        }
+        let attack = |i, j, a, b| action::Action::Attack { i, j, a, b };
+        // Dead hands, the wrong turn, self attacks, and out of bounds indexes
+        for action in [
+            attack(0, 1, 0, 1),
+            attack(1, 0, 0, 0),
+            attack(0, 0, 0, 1),
+            attack(0, 2, 0, 0),
+            attack(0, 1, 2, 0),
+        ] {
+            assert!(!game_state.is_legal(&action));
+        }
+        let split = |i, hands_0, hands_1| action::Action::Split {
+            i,
+            hands_0,
+            hands_1,
+        };
+        assert!(game_state.is_legal(&split(0, [1, 4], [2, 3])));
+        for action in [
+            split(0, [1, 4], [4, 1]),
+            split(0, [1, 4], [1, 3]),
+            split(0, [2, 3], [1, 4]),
+            split(2, [1, 4], [2, 3]),
+        ] {
+            assert!(!game_state.is_legal(&action));
+        }
+        let finished = State::<2, Chopsticks>::from_abbreviation("1400").expect("valid");
+        assert!(!finished.is_legal(&split(0, [1, 4], [2, 3])));
+    }
+
+    #[test]
+    fn infer_action() {
+        let game_state = Chopsticks.get_initial_state();
+        let next = State::<2, Chopsticks>::with_hands([[1, 1], [1, 2]], 1).expect("valid");
+        assert_eq!(
+            game_state.infer_action(&next),
+            Some(action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 0,
+                b: 1
+            })
+        );
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 3], [1, 1]], 0).expect("valid");
+        let next = State::<2, Chopsticks>::with_hands([[2, 2], [1, 1]], 1).expect("valid");
+        assert_eq!(
+            game_state.infer_action(&next),
+            Some(action::Action::Split {
+                i: 0,
+                hands_0: [1, 3],
+                hands_1: [2, 2]
+            })
+        );
+        // Same hands but still player 0's turn
+        let next = State::<2, Chopsticks>::with_hands([[2, 2], [1, 1]], 0).expect("valid");
+        assert_eq!(game_state.infer_action(&next), None);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1300").expect("valid");
+        let next = State::<2, Chopsticks>::with_hands([[2, 2], [0, 0]], 1).expect("valid");
+        assert_eq!(game_state.infer_action(&next), None);
+    }
+
+    #[test]
+    fn actions_for() {
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 3], [2, 2]], 0).expect("valid");
+        assert!(game_state.iter_actions_for(0).eq(game_state.iter_actions()));
+        let actions: Vec<_> = game_state.iter_actions_for(1).collect();
+        assert_eq!(game_state.i, 0);
+        let mut turn_1 = game_state.clone();
+        turn_1.set_turn(1);
+        assert_eq!(actions, turn_1.iter_actions().collect::<Vec<_>>());
+        assert!(actions.iter().all(|action| action.get_i() == 1));
+        assert!(actions.contains(&action::Action::Split {
+            i: 1,
+            hands_0: [2, 2],
+            hands_1: [1, 3],
+        }));
+    }
+
+    #[test]
+    fn parse_action() {
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 3], [2, 2]], 1).expect("valid");
+        let attack = action::Action::Attack {
+            i: 1,
+            j: 0,
+            a: 1,
+            b: 1,
+        };
+        assert_eq!(game_state.parse_action("a 0 1 1").expect("valid"), attack);
+        assert_eq!(game_state.parse_action("a 1 0 1 1").expect("valid"), attack);
+        let split = action::Action::Split {
+            i: 1,
+            hands_0: [2, 2],
+            hands_1: [1, 3],
+        };
+        assert_eq!(game_state.parse_action("s 1 3").expect("valid"), split);
+        assert_eq!(
+            game_state.parse_action("s 1 2 2 1 3").expect("valid"),
+            split
+        );
+        assert_eq!(
+            game_state.parse_action("p").expect("valid"),
+            action::Action::Pass { i: 1 }
+        );
+        assert!(matches!(
+            game_state.parse_action("a 0 1"),
+            Err(action::ParseActionError::WrongTokenCount)
+        ));
+        assert!(matches!(
+            game_state.parse_action("s 1 x"),
+            Err(action::ParseActionError::InvalidNumber)
+        ));
+    }
+
+    #[test]
+    fn legal_actions() {
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 3], [2, 2]], 0).expect("valid");
+        let legal = game_state.legal_actions();
+        assert_eq!(legal.attacks.len(), 4);
+        assert_eq!(legal.splits.len(), 1);
+        assert!(legal
+            .attacks
+            .iter()
+            .all(|action| matches!(action, action::Action::Attack { .. })));
+        assert!(legal
+            .splits
+            .iter()
+            .all(|action| matches!(action, action::Action::Split { .. })));
+        assert_eq!(legal.pass, None);
+        let actions: Vec<_> = legal.attacks.into_iter().chain(legal.splits).collect();
+        assert_eq!(actions, game_state.iter_actions().collect::<Vec<_>>());
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Passing;
+
+    impl StateSpace<2> for Passing {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const ALLOW_PASS: bool = true;
+    }
+
+    #[test]
+    fn pass_actions() {
+        let mut game_state = Passing.get_initial_state();
+        let pass = action::Action::Pass { i: 0 };
+        assert_eq!(game_state.iter_actions().last(), Some(pass));
+        assert_eq!(
+            game_state.count_actions(),
+            game_state.iter_actions().count()
+        );
+        let n = game_state.count_actions() - 1;
+        assert_eq!(game_state.nth_action(n), Some(pass));
+        assert_eq!(game_state.legal_actions().pass, Some(pass));
+        assert!(game_state.is_legal(&pass));
+        assert!(game_state.play_action(&pass).is_ok());
+        assert_eq!(game_state.i, 1);
+        assert_eq!(game_state.players, Passing.get_initial_state().players);
+        assert!(matches!(
+            game_state.play_action(&pass),
+            Err(action::ActionError::WrongTurn)
+        ));
+        assert!(game_state.undo_action(&pass).is_ok());
+        assert_eq!(game_state, Passing.get_initial_state());
+
+        let mut game_state = Chopsticks.get_initial_state();
+        assert!(!game_state.is_legal(&action::Action::Pass { i: 0 }));
+        assert!(matches!(
+            game_state.play_action(&action::Action::Pass { i: 0 }),
+            Err(action::ActionError::PassNotAllowed)
+        ));
+    }
+
+    #[test]
+    fn winning_actions() {
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        let winning: Vec<_> = game_state.iter_winning_actions().collect();
+        assert_eq!(
+            winning,
+            [action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 1,
+                b: 0
+            }]
+        );
+        assert_eq!(game_state.iter_killing_attacks().count(), 1);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1314").expect("valid");
+        assert_eq!(game_state.iter_winning_actions().count(), 0);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1400").expect("valid");
+        assert_eq!(game_state.iter_winning_actions().count(), 0);
+    }
+
+    #[test]
+    fn attack_by_value() {
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("1314").expect("valid");
+        assert!(game_state.play_attack_by_value(0, 1, 3, 4).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "1312");
+        // Equal hands resolve to the first
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("2211").expect("valid");
+        assert!(game_state.play_attack_by_value(0, 1, 2, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2231");
+        assert!(matches!(
+            game_state.play_attack_by_value(1, 0, 1, 3),
+            Err(action::AttackError::HandValueNotFound)
+        ));
+        let mut game_state =
+            State::<2, SelfAttack>::with_hands([[2, 2], [1, 1]], 0).expect("valid");
+        assert!(game_state.play_attack_by_value(0, 0, 2, 2).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2411");
+    }
+
+    #[test]
+    fn killing_attacks() {
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 3], [1, 4]], 0).expect("valid");
+        let killing: Vec<_> = game_state.iter_killing_attacks().collect();
+        assert_eq!(
+            killing,
+            [action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 0,
+                b: 1
+            }]
+        );
+        let game_state =
+            State::<2, CutoffChopsticks>::with_hands([[3, 1], [3, 4]], 0).expect("valid");
+        assert_eq!(game_state.iter_killing_attacks().count(), 1);
+        let game_state = State::<2, Chopsticks>::default();
+        assert_eq!(game_state.iter_killing_attacks().count(), 0);
+    }
+
+    #[test]
+    fn cutoff_stalemate() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut game_state =
+            State::<2, CutoffChopsticks>::with_hands([[3, 4], [3, 4]], 1).expect("valid");
+        assert!(!game_state.is_terminal());
+        let actions: Vec<_> = game_state.iter_actions().collect();
+        assert_eq!(actions, [action::Action::Pass { i: 1 }]);
+        assert!(game_state
+            .simulate_random(&mut StdRng::seed_from_u64(0))
+            .is_none());
+        assert!(game_state.play_action(&actions[0]).is_ok());
+        assert_eq!(
+            game_state.get_pass_action(),
+            Some(action::Action::Pass { i: 0 })
+        );
+        assert!(matches!(
+            State::<2, CutoffChopsticks>::default().play_action(&action::Action::Pass { i: 0 }),
+            Err(action::ActionError::PassNotAllowed)
+        ));
+        let mut initial = State::<2, CutoffChopsticks>::default();
+        for seed in 0..50 {
+            initial.simulate_random(&mut StdRng::seed_from_u64(seed));
+        }
+        let outcomes = crate::solver::solve(&CutoffChopsticks);
+        let serial = CutoffChopsticks::serialize_state(&game_state);
+        assert!(matches!(
+            outcomes.get(&serial),
+            Some((crate::solver::Outcome::Draw, _))
+        ));
+    }
+
+    #[test]
+    fn with_hands_invalid() {
+        assert!(matches!(
+            State::<2, Chopsticks>::with_hands([[1, 1], [1, 1]], 2),
+            Err(ValueError::PlayerIndexOutOfBounds)
+        ));
+        assert!(matches!(
+            State::<2, Chopsticks>::with_hands([[1, 1], [5, 1]], 0),
+            Err(ValueError::FingerValueOutOfBounds)
+        ));
+        let game_state = State::<2, Chopsticks>::with_hands([[0, 4], [2, 3]], 1).expect("valid");
+        assert_eq!(game_state.i, 1);
+        assert_eq!(game_state.get_abbreviation(), "0423");
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct SelfAttack;
+
+    impl StateSpace<2> for SelfAttack {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const ALLOW_SELF_ATTACK: bool = true;
+    }
+
+    #[test]
+    fn self_attack() {
+        let mut game_state =
+            State::<2, SelfAttack>::with_hands([[2, 3], [1, 1]], 0).expect("valid");
+        assert_eq!(game_state.iter_attack_actions().count(), 6);
+        assert!(matches!(
+            game_state.play_attack(0, 0, 1, 1),
+            Err(action::AttackError::PlayerAttackSelf)
+        ));
+        assert!(game_state.play_attack(0, 0, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2011");
+        assert_eq!(game_state.i, 1);
+        assert!(game_state.undo_attack(0, 0, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2311");
+        assert_eq!(game_state.i, 0);
+        assert!(game_state.count_actions() <= SelfAttack::MAX_ACTIONS);
+    }
+
+    #[test]
+    fn split_with_zero() {
+        let mut game_state = Chopsticks.get_initial_state();
+        assert!(game_state.play_split(0, [1, 1], [0, 2]).is_err());
+        assert!(game_state.play_split(0, [1, 1], [2, 0]).is_err());
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Revive;
+
+    impl StateSpace<2> for Revive {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const ALLOW_REVIVE: bool = true;
+    }
+
+    #[test]
+    fn revive_split() {
+        let mut game_state = State::<2, Revive>::with_hands([[2, 2], [1, 1]], 0).expect("valid");
+        assert_eq!(game_state.iter_split_actions().count(), 2);
+        assert_eq!(
+            game_state.count_actions(),
+            game_state.iter_actions().count()
+        );
+        assert!(game_state.count_actions() <= Revive::MAX_ACTIONS);
+        assert!(game_state.play_split(0, [2, 2], [0, 4]).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "0411");
+        assert!(game_state.undo_split(0, [2, 2], [0, 4]).is_ok());
+        let mut game_state =
+            State::<2, Chopsticks>::with_hands([[2, 2], [1, 1]], 0).expect("valid");
+        assert!(matches!(
+            game_state.play_split(0, [2, 2], [0, 4]),
+            Err(action::SplitError::InvalidFingerValue)
+        ));
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Thumb;
+
+    impl StateSpace<2> for Thumb {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const ROLLOVERS: Option<[u32; 2]> = Some([5, 4]);
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct ShortThumb;
+
+    impl StateSpace<2> for ShortThumb {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const ROLLOVERS: Option<[u32; 2]> = Some([5, 3]);
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn short_thumb_undo() {
+        // A 4 on a 2 wraps past the thumb's rollover of 3 to kill it
+        let mut game_state =
+            State::<2, ShortThumb>::with_hands([[4, 1], [1, 2]], 0).expect("valid");
+        assert!(game_state.play_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "4110");
+        assert!(game_state.undo_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "4112");
+        let reachable = ShortThumb.get_initial_state().iter_reachable().count();
+        assert_eq!(crate::solver::solve(&ShortThumb).len(), reachable);
+    }
+
+    #[test]
+    fn short_thumb_canonical() {
+        let mut game_state =
+            State::<2, ShortThumb>::with_hands([[4, 1], [2, 1]], 0).expect("valid");
+        let swapped = State::<2, ShortThumb>::with_hands([[4, 1], [1, 2]], 0).expect("valid");
+        assert_ne!(game_state.canonical_serial(), swapped.canonical_serial());
+        game_state.canonicalize();
+        assert_eq!(game_state.get_abbreviation(), "4121");
+    }
+
+    #[test]
+    fn per_hand_rollovers() {
+        assert!(matches!(
+            State::<2, Thumb>::with_hands([[1, 4], [1, 1]], 0),
+            Err(ValueError::FingerValueOutOfBounds)
+        ));
+        let mut game_state = State::<2, Thumb>::with_hands([[1, 1], [3, 3]], 0).expect("valid");
+        assert_eq!(game_state.iter_killing_attacks().count(), 2);
+        assert!(game_state.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "1143");
+        assert!(game_state.undo_attack(0, 1, 0, 0).is_ok());
+        assert!(game_state.play_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "1130");
+        assert!(game_state.undo_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "1133");
+
+        // Hands are not interchangeable so swapping them is a split
+        let game_state = State::<2, Thumb>::with_hands([[2, 2], [1, 1]], 0).expect("valid");
+        let splits: Vec<_> = game_state.iter_split_actions().collect();
+        assert!(matches!(
+            splits.as_slice(),
+            [
+                action::Action::Split {
+                    hands_1: [1, 3],
+                    ..
+                },
+                action::Action::Split {
+                    hands_1: [3, 1],
+                    ..
+                },
+            ]
+        ));
+        let mut game_state = State::<2, Thumb>::with_hands([[3, 3], [1, 1]], 0).expect("valid");
+        assert_eq!(game_state.iter_split_actions().count(), 1);
+        assert!(matches!(
+            game_state.play_split(0, [3, 3], [2, 4]),
+            Err(action::SplitError::InvalidFingerValue)
+        ));
+        assert!(game_state.play_split(0, [3, 3], [4, 2]).is_ok());
+        assert_eq!(
+            game_state.count_actions(),
+            game_state.iter_actions().count()
+        );
+        assert!(game_state.count_actions() <= Thumb::MAX_ACTIONS);
+    }
+
+    #[test]
+    fn split_actions_total_four() {
+        // Every unordered arrangement of 4 fingers other than the current one, each once
+        let expected: [(&str, &[[u32; 2]]); 5] = [
+            ("0411", &[[1, 3], [2, 2]]),
+            ("4011", &[[1, 3], [2, 2]]),
+            ("1311", &[[2, 2]]),
+            ("3111", &[[2, 2]]),
+            ("2211", &[[1, 3]]),
+        ];
+        for (abbreviation, splits) in expected {
+            let game_state =
+                State::<2, Chopsticks>::from_abbreviation(abbreviation).expect("valid");
+            let hands: Vec<_> = game_state
+                .iter_split_actions()
+                .map(|action| match action {
+                    action::Action::Split { hands_1, .. } => hands_1,
+                    _ => panic!("expect split"),
+                })
+                .collect();
+            assert_eq!(hands, splits, "{abbreviation}");
+        }
+        let game_state = State::<2, Revive>::with_hands([[1, 3], [1, 1]], 0).expect("valid");
+        assert_eq!(game_state.iter_split_actions().count(), 2);
+    }
+
+    #[test]
+    fn split_with_five() {
+        let mut game_state = Chopsticks.get_initial_state();
+        game_state.set_hands(0, [4; 2]);
+        assert!(game_state.play_split(0, [4, 4], [5, 3]).is_err());
+        assert!(game_state.play_split(0, [4, 4], [3, 5]).is_err());
+    }
+
+    #[test]
     fn split_invalid_total() {
         let mut game_state = Chopsticks.get_initial_state();
         assert!(game_state.play_split(0, [1, 1], [1, 2]).is_err());
     }
 
+    #[test]
+    fn split_slice() {
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("1311").expect("valid");
+        assert!(matches!(
+            game_state.play_split_slice(0, &[4]),
+            Err(action::SplitError::InvalidHandLen)
+        ));
+        assert!(matches!(
+            game_state.play_split_slice(0, &[2, 1, 1]),
+            Err(action::SplitError::InvalidHandLen)
+        ));
+        assert!(matches!(
+            game_state.play_split_slice(2, &[2, 2]),
+            Err(action::SplitError::ImproperContext)
+        ));
+        assert!(game_state.play_split_slice(0, &[2, 2]).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2211");
+    }
+
     #[test]
     fn split_no_update() {
         let mut game_state = Chopsticks.get_initial_state();
-        game_state.players[0].hands[1] = 2;
+        game_state.set_hands(0, [1, 2]);
         assert!(game_state.play_split(0, [1, 2], [1, 2]).is_err());
         assert!(game_state.play_split(0, [1, 2], [2, 1]).is_err());
     }
 
+    #[test]
+    fn split_rollover_total_no_update() {
+        let mut game_state = Chopsticks.get_initial_state();
+        for (a, b) in [(1, 4), (4, 1), (2, 3), (3, 2)] {
+            game_state.set_hands(0, [a, b]);
+            assert!(matches!(
+                game_state.play_split(0, [a, b], [b, a]),
+                Err(action::SplitError::MoveWithoutChange)
+            ));
+            assert!(matches!(
+                game_state.play_split(0, [a, b], [a, b]),
+                Err(action::SplitError::MoveWithoutChange)
+            ));
+        }
+    }
+
+    #[test]
+    fn split_rollover_total_wrap() {
+        let mut game_state = Chopsticks.get_initial_state();
+        game_state.set_hands(0, [1, 4]);
+        assert!(matches!(
+            game_state.play_split(0, [1, 4], [0, 5]),
+            Err(action::SplitError::InvalidFingerValue)
+        ));
+        assert!(matches!(
+            game_state.play_split(0, [1, 4], [5, 0]),
+            Err(action::SplitError::InvalidFingerValue)
+        ));
+        assert!(matches!(
+            game_state.play_split(0, [1, 4], [0, 0]),
+            Err(action::SplitError::InvalidTotalFingers)
+        ));
+    }
+
+    #[test]
+    fn split_rollover_total() {
+        let mut game_state = Chopsticks.get_initial_state();
+        for (a, b, c, d) in [(1, 4, 2, 3), (1, 4, 3, 2), (2, 3, 1, 4), (2, 3, 4, 1)] {
+            game_state.set_turn(0);
+            game_state.set_hands(0, [a, b]);
+            assert!(game_state.play_split(0, [a, b], [c, d]).is_ok());
+            assert_eq!(game_state.players[0].hands, [c, d]);
+            assert!(game_state.undo_split(0, [a, b], [c, d]).is_ok());
+            assert_eq!(game_state.players[0].hands, [a, b]);
+        }
+    }
+
+    #[test]
+    fn split_actions_rollover_total() {
+        let mut game_state = Chopsticks.get_initial_state();
+        game_state.set_hands(0, [1, 4]);
+        let actions: Vec<_> = game_state.iter_split_actions().collect();
+        assert_eq!(
+            actions,
+            [action::Action::Split {
+                i: 0,
+                hands_0: [1, 4],
+                hands_1: [2, 3]
+            }]
+        );
+    }
+
     #[test]
     fn valid_splits() {
         let mut game_state = Chopsticks.get_initial_state();
@@ -389,14 +2058,134 @@ mod tests {
             (2, 4, 3, 3),
             (3, 3, 2, 4),
         ] {
-            game_state.players[0].hands[0] = a;
-            game_state.players[0].hands[1] = b;
+            game_state.set_hands(0, [a, b]);
             assert!(game_state.play_split(0, [a, b], [c, d]).is_ok());
             assert_eq!(game_state.players[0].hands[0], c);
             assert_eq!(game_state.players[0].hands[1], d);
         }
     }
 
+    #[test]
+    fn count_actions() {
+        for abbreviation in ["1111", "1410", "0401", "2323", "0000", "4444", "1234"] {
+            let game_state =
+                State::<2, Chopsticks>::from_abbreviation(abbreviation).expect("valid");
+            assert_eq!(
+                game_state.count_actions(),
+                game_state.iter_actions().count(),
+                "{abbreviation}"
+            );
+        }
+        let game_state = State::<2, ThreeHanded>::from_abbreviation("104222").expect("valid");
+        assert_eq!(
+            game_state.count_actions(),
+            game_state.iter_actions().count()
+        );
+    }
+
+    #[test]
+    fn branching_factor() {
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1304").expect("valid");
+        assert!(!game_state.is_terminal());
+        assert_eq!(game_state.branching_factor(), 3);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1400").expect("valid");
+        assert!(game_state.is_terminal());
+        assert_eq!(game_state.branching_factor(), 0);
+    }
+
+    #[test]
+    fn nth_action() {
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1304").expect("valid");
+        let actions: Vec<_> = game_state.iter_actions().collect();
+        for (n, action) in actions.iter().enumerate() {
+            assert_eq!(game_state.nth_action(n).as_ref(), Some(action));
+        }
+        assert_eq!(game_state.nth_action(actions.len()), None);
+    }
+
+    /// `count_actions` and `nth_action` agree with `iter_actions` from every reachable `State`
+    fn indexed_actions<const N: usize, T: StateSpace<N> + std::fmt::Debug>(state_space: T) {
+        for game_state in state_space.get_initial_state().iter_reachable() {
+            let actions: Vec<_> = game_state.iter_actions().collect();
+            assert_eq!(game_state.count_actions(), actions.len());
+            for (n, action) in actions.iter().enumerate() {
+                assert_eq!(game_state.nth_action(n).as_ref(), Some(action));
+            }
+            assert_eq!(game_state.nth_action(actions.len()), None);
+        }
+    }
+
+    #[test]
+    fn indexed_actions_variants() {
+        indexed_actions(Chopsticks);
+        indexed_actions(ThreeHanded);
+        indexed_actions(Revive);
+        indexed_actions(Thumb);
+        indexed_actions(Passing);
+        indexed_actions(CutoffChopsticks);
+    }
+
+    #[test]
+    fn three_handed_attacks() {
+        let mut game_state = ThreeHanded.get_initial_state();
+        assert_eq!(game_state.get_abbreviation(), "111111");
+        assert!(matches!(
+            game_state.play_attack(0, 1, 0, 3),
+            Err(action::AttackError::HandIndexOutOfBounds)
+        ));
+        assert!(game_state.play_attack(0, 1, 2, 2).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "111112");
+        assert!(game_state.play_attack(1, 0, 2, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "131112");
+        assert!(game_state.undo_attack(1, 0, 2, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "111112");
+        assert_eq!(game_state.iter_attack_actions().count(), 9);
+    }
+
+    #[test]
+    fn three_handed_splits() {
+        let mut game_state = ThreeHanded.get_initial_state();
+        game_state.set_hands(0, [0, 1, 3]);
+        let splits: Vec<_> = game_state
+            .iter_split_actions()
+            .map(|action| match action {
+                action::Action::Split { hands_1, .. } => hands_1,
+                _ => panic!("expect split"),
+            })
+            .collect();
+        assert_eq!(splits, [[1, 1, 2]]);
+        assert!(game_state.play_split(0, [0, 1, 3], [2, 1, 1]).is_ok());
+        assert_eq!(game_state.players[0].hands, [2, 1, 1]);
+    }
+
+    #[test]
+    fn iterate_turn_after_elimination() {
+        let mut game_state = State::<3, ThreePlayers>::from_abbreviation("411011").expect("valid");
+        assert!(game_state.play_attack(0, 1, 0, 0).is_ok()); // 410011
+        assert!(game_state.players[1].is_eliminated());
+        assert_eq!(game_state.i, 2);
+        assert!(game_state.play_attack(2, 0, 0, 1).is_ok()); // 420011
+        assert_eq!(game_state.i, 0);
+        assert!(game_state.undo_attack(2, 0, 0, 1).is_ok());
+        assert_eq!(game_state.i, 2);
+        assert!(game_state.undo_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.i, 0);
+        assert_eq!(game_state.get_abbreviation(), "411011");
+    }
+
+    #[test]
+    fn undo_winning_attack() {
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        assert!(game_state.play_attack(0, 1, 1, 0).is_ok());
+        assert!(matches!(
+            game_state.get_status(),
+            status::Status::Over { i: 0 }
+        ));
+        assert!(game_state.undo_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(game_state.i, 0);
+        assert_eq!(game_state.get_abbreviation(), "1410");
+    }
+
     #[test]
     fn no_winner_id() {
         let game_state = Chopsticks.get_initial_state();
@@ -406,9 +2195,553 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn from_abbreviation() {
+        let state = State::<2, Chopsticks>::from_abbreviation("1111").expect("valid");
+        assert_eq!(state, Chopsticks.get_initial_state());
+        let state = State::<2, Chopsticks>::from_abbreviation("1340").expect("valid");
+        assert_eq!(state.players[0].hands, [1, 3]);
+        assert_eq!(state.players[1].hands, [4, 0]);
+        assert_eq!(state.get_abbreviation(), "1340");
+    }
+
+    #[test]
+    fn from_abbreviation_malformed() {
+        assert!(matches!(
+            State::<2, Chopsticks>::from_abbreviation("abc"),
+            Err(ParseError::InvalidLength)
+        ));
+        assert!(matches!(
+            State::<2, Chopsticks>::from_abbreviation("abcd"),
+            Err(ParseError::InvalidCharacter)
+        ));
+        assert!(matches!(
+            State::<2, Chopsticks>::from_abbreviation("11111"),
+            Err(ParseError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn from_abbreviation_out_of_range() {
+        assert!(matches!(
+            State::<2, Chopsticks>::from_abbreviation("1115"),
+            Err(ParseError::InvalidFingerValue)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let state = Chopsticks.get_initial_state();
+        let json = serde_json::to_string(&state).expect("serializable");
+        assert_eq!(
+            json,
+            r#"{"i":0,"players":[{"hands":[1,1]},{"hands":[1,1]}]}"#
+        );
+        let deserialized: State<2, Chopsticks> = serde_json::from_str(&json).expect("valid");
+        assert_eq!(deserialized, state);
+        assert!(matches!(
+            deserialized.get_status(),
+            status::Status::Turn { i: 0 }
+        ));
+        assert!(serde_json::from_str::<State<2, Chopsticks>>(r#"{"i":0,"players":[]}"#).is_err());
+    }
+
+    #[test]
+    fn canonical_serial() {
+        let state_13 = State::<2, Chopsticks>::from_abbreviation("1322").expect("valid");
+        let state_31 = State::<2, Chopsticks>::from_abbreviation("3122").expect("valid");
+        let state_14 = State::<2, Chopsticks>::from_abbreviation("1422").expect("valid");
+        assert_ne!(
+            Chopsticks::serialize_state(&state_13),
+            Chopsticks::serialize_state(&state_31)
+        );
+        assert_eq!(state_13.canonical_serial(), state_31.canonical_serial());
+        assert_ne!(state_13.canonical_serial(), state_14.canonical_serial());
+        let mut state = state_31.clone();
+        state.cache_serial();
+        state.canonicalize();
+        assert_eq!(state.get_abbreviation(), "1322");
+        assert_eq!(
+            state.cached_serial(),
+            Chopsticks::serialize_state(&state_13)
+        );
+    }
+
+    #[test]
+    fn iter_reachable() {
+        let game_state = Chopsticks.get_initial_state();
+        let states: Vec<_> = game_state.iter_reachable().collect();
+        let serials: HashSet<_> = states.iter().map(Chopsticks::serialize_state).collect();
+        let canonical: HashSet<_> = states.iter().map(State::canonical_serial).collect();
+        assert_eq!(states[0].get_abbreviation(), "1111");
+        assert_eq!(serials.len(), states.len());
+        assert_eq!(states.len(), 1178);
+        assert_eq!(canonical.len(), 408);
+    }
+
+    #[test]
+    fn rotate_to_current() {
+        let mut game_state = Chopsticks.get_initial_state();
+        assert!(game_state.play_attack(0, 1, 0, 1).is_ok()); // 1112
+        let (rotated, permutation) = game_state.rotate_to_current();
+        assert_eq!(rotated.i, 0);
+        assert_eq!(rotated.get_abbreviation(), "1211");
+        assert_eq!(permutation, [1, 0]);
+        let mut players = rotated.players.clone();
+        for (k, &j) in permutation.iter().enumerate() {
+            players[j] = rotated.players[k].clone();
+        }
+        let restored = State::from_players(permutation[rotated.i], players);
+        assert_eq!(restored, game_state);
+    }
+
+    #[test]
+    fn undo_with() {
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("1312").expect("valid");
+        let before = game_state.clone();
+        let history = [
+            action::Action::Split {
+                i: 0,
+                hands_0: [1, 3],
+                hands_1: [2, 2],
+            },
+            action::Action::Attack {
+                i: 1,
+                j: 0,
+                a: 1,
+                b: 0,
+            },
+            action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 1,
+                b: 1,
+            },
+            action::Action::Attack {
+                i: 1,
+                j: 0,
+                a: 0,
+                b: 1,
+            },
+            action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 0,
+                b: 0,
+            },
+        ];
+        let mut tokens = Vec::new();
+        for action in history.iter() {
+            tokens.push(game_state.play_action_reversible(action).expect("valid"));
+        }
+        assert_eq!(game_state.get_abbreviation(), "4304");
+        assert!(matches!(
+            game_state.play_action_reversible(&history[0]),
+            Err(action::ActionError::WrongTurn)
+        ));
+        while let Some(token) = tokens.pop() {
+            game_state.undo_with(token);
+        }
+        assert_eq!(game_state, before);
+    }
+
+    #[test]
+    fn display() {
+        let game_state = Chopsticks.get_initial_state();
+        assert_eq!(game_state.to_string(), "P0: [1,1]  <- turn\nP1: [1,1]");
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 4], [0, 0]], 1).expect("valid");
+        assert_eq!(game_state.to_string(), "P0: [1,4]\nP1: [0,0]");
+    }
+
+    #[test]
+    fn pack_round_trip() {
+        for game_state in Chopsticks.get_initial_state().iter_reachable() {
+            let bits = game_state.pack();
+            assert!(bits < 1 << 13);
+            assert_eq!(State::unpack(bits), Some(game_state));
+        }
+        let game_state = State::<3, ThreePlayers>::with_hands([[0, 1], [2, 3], [4, 0]], 2);
+        let game_state = game_state.expect("valid");
+        assert_eq!(State::unpack(game_state.pack()), Some(game_state));
+    }
+
+    #[test]
+    fn unpack_out_of_bounds() {
+        // Hand 0 of player 0 has 5 fingers
+        assert_eq!(State::<2, Chopsticks>::unpack(5 << 1), None);
+        assert_eq!(State::<2, Chopsticks>::unpack(1 << 13), None);
+        // Player 3 does not exist
+        assert_eq!(State::<3, ThreePlayers>::unpack(3), None);
+        assert!(State::<3, ThreePlayers>::unpack(2).is_some());
+    }
+
+    #[test]
+    fn serial_round_trip() {
+        for (hands, i) in [
+            ([[1, 1], [1, 1]], 0),
+            ([[1, 3], [2, 2]], 1),
+            ([[0, 4], [4, 0]], 0),
+            ([[4, 4], [0, 1]], 1),
+        ] {
+            let game_state = State::<2, Chopsticks>::with_hands(hands, i).expect("valid");
+            let serial: u16 = game_state.serial();
+            assert_eq!(State::from_serial(serial), Some(game_state.clone()));
+            let serial: u64 = game_state.serial();
+            assert_eq!(State::from_serial(serial), Some(game_state));
+        }
+        assert_eq!(
+            State::<2, Chopsticks>::from_serial(Chopsticks::STATE_SERIAL_BASE),
+            None
+        );
+        assert_eq!(State::<2, Chopsticks>::from_serial(-1), None);
+        // Hand 1 of player 0 has 4 fingers which is its rollover
+        assert_eq!(State::<2, Thumb>::from_serial(4 * 5 * 2), None);
+    }
+
+    #[test]
+    fn cached_serial() {
+        use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut game_state = State::<3, ThreePlayers>::default();
+        game_state.cache_serial();
+        let mut history = Vec::new();
+        for _ in 0..200 {
+            let undo = !history.is_empty() && (game_state.is_terminal() || rng.gen_bool(0.3));
+            if undo {
+                let action = history.pop().expect("played action");
+                assert!(game_state.undo_action(&action).is_ok());
+            } else {
+                let action = game_state.iter_actions().choose(&mut rng).expect("action");
+                assert!(game_state.play_action(&action).is_ok());
+                history.push(action);
+            }
+            assert_eq!(
+                game_state.running_serial,
+                Some(ThreePlayers::serialize_state(&game_state))
+            );
+            assert_eq!(
+                game_state.cached_serial(),
+                ThreePlayers::serialize_state(&game_state)
+            );
+        }
+    }
+
+    #[test]
+    fn play_simultaneous() {
+        let attack = |i, j, a, b| action::Action::Attack { i, j, a, b };
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("1231").expect("valid");
+        // Player 1 still attacks with the hand player 0 kills
+        assert!(game_state
+            .play_simultaneous([attack(0, 1, 1, 0), attack(1, 0, 0, 1)])
+            .is_ok());
+        assert_eq!(game_state.get_abbreviation(), "1001");
+        assert_eq!(game_state.i, 0);
+        assert!(matches!(
+            game_state.play_simultaneous([attack(0, 1, 0, 1), attack(0, 1, 0, 1)]),
+            Err(action::ActionError::WrongTurn)
+        ));
+        assert!(matches!(
+            game_state.play_simultaneous([attack(0, 1, 1, 1), attack(1, 0, 1, 0)]),
+            Err(action::ActionError::AttackError(
+                action::AttackError::HandIsNotAlive
+            ))
+        ));
+        assert_eq!(game_state.get_abbreviation(), "1001");
+        // Both attacks kill the last hand of the other player
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("0104").expect("valid");
+        assert!(matches!(
+            game_state.play_simultaneous([attack(0, 1, 1, 1), attack(1, 0, 1, 1)]),
+            Err(action::ActionError::NotSimultaneous)
+        ));
+        assert_eq!(game_state.get_abbreviation(), "0104");
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("0101").expect("valid");
+        assert!(matches!(
+            game_state.play_simultaneous([
+                attack(0, 1, 1, 1),
+                action::Action::Split {
+                    i: 1,
+                    hands_0: [0, 1],
+                    hands_1: [1, 0],
+                }
+            ]),
+            Err(action::ActionError::NotSimultaneous)
+        ));
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct CutoffThreePlayers;
+
+    impl StateSpace<3> for CutoffThreePlayers {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const ROLLOVER_MODE: RolloverMode = RolloverMode::Cutoff;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn play_simultaneous_cutoff() {
+        let attack = |i, j, a, b| action::Action::Attack { i, j, a, b };
+        let mut game_state =
+            State::<3, CutoffThreePlayers>::from_abbreviation("232311").expect("valid");
+        // Each attack is within the rollover but together they exceed it
+        assert!(matches!(
+            game_state.play_simultaneous([
+                attack(0, 2, 1, 0),
+                attack(1, 2, 1, 0),
+                attack(2, 0, 0, 0)
+            ]),
+            Err(action::ActionError::AttackError(
+                action::AttackError::ExceedsRollover
+            ))
+        ));
+        assert_eq!(game_state.get_abbreviation(), "232311");
+        // Together they reach the rollover exactly and kill the hand
+        assert!(game_state
+            .play_simultaneous([attack(0, 2, 0, 0), attack(1, 2, 0, 0), attack(2, 0, 0, 1)])
+            .is_ok());
+        assert_eq!(game_state.get_abbreviation(), "242301");
+        assert_eq!(game_state.i, 0);
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct WrapByFour;
+
+    impl StateSpace<2> for WrapByFour {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const ROLLOVER_ADD: u32 = 4;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn rollover_add() {
+        let mut game_state = State::<2, WrapByFour>::from_abbreviation("3134").expect("valid");
+        assert!(game_state.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "3124");
+        assert!(game_state.undo_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "3134");
+        assert!(game_state.play_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "3133");
+        assert!(game_state.undo_attack(0, 1, 0, 1).is_ok());
+        // Exactly `FINGERS` still kills
+        assert!(game_state.play_attack(0, 1, 1, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "3130");
+        assert!(game_state.undo_attack(0, 1, 1, 1).is_ok());
+        assert_eq!(game_state.iter_killing_attacks().count(), 1);
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("3134").expect("valid");
+        assert!(game_state.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "3114");
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct CutoffFourFingers;
+
+    impl StateSpace<2> for CutoffFourFingers {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const FINGERS: u32 = 4;
+        const ROLLOVER_MODE: RolloverMode = RolloverMode::Cutoff;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn cutoff_fingers() {
+        let attack = |i, j, a, b| action::Action::Attack { i, j, a, b };
+        let mut game_state =
+            State::<2, CutoffFourFingers>::from_abbreviation("2222").expect("valid");
+        let mut single = game_state.clone();
+        assert!(single.play_attack(0, 1, 0, 0).is_ok());
+        assert_eq!(single.get_abbreviation(), "2202");
+        assert!(game_state
+            .play_simultaneous([attack(0, 1, 0, 0), attack(1, 0, 0, 0)])
+            .is_ok());
+        assert_eq!(game_state.get_abbreviation(), "0202");
+        let mut game_state =
+            State::<2, CutoffFourFingers>::from_abbreviation("3222").expect("valid");
+        assert!(matches!(
+            game_state.clone().play_attack(0, 1, 0, 0),
+            Err(action::AttackError::ExceedsRollover)
+        ));
+        assert!(matches!(
+            game_state.play_simultaneous([attack(0, 1, 0, 0), attack(1, 0, 0, 1)]),
+            Err(action::ActionError::AttackError(
+                action::AttackError::ExceedsRollover
+            ))
+        ));
+    }
+
+    /// Attacks take the attacker's fingers away modulo `ROLLOVER`
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Subtracting;
+
+    impl StateSpace<2> for Subtracting {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+
+        fn resolve_attack(defender: u32, attacker: u32, _b: usize) -> u32 {
+            (defender + Self::ROLLOVER - attacker) % Self::ROLLOVER
+        }
+
+        fn unresolve_attack(fingers: u32, attacker: u32, _b: usize) -> Option<u32> {
+            let before = (fingers + attacker) % Self::ROLLOVER;
+            (before != 0).then_some(before)
+        }
+    }
+
+    #[test]
+    fn resolve_attack() {
+        let mut game_state = State::<2, Subtracting>::from_abbreviation("2413").expect("valid");
+        assert!(game_state.play_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2411");
+        assert!(game_state.undo_attack(0, 1, 0, 1).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2413");
+        assert!(game_state.play_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "2423");
+        assert!(game_state.undo_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(game_state.iter_killing_attacks().count(), 0);
+        let game_state = Subtracting.get_initial_state();
+        assert!(game_state
+            .iter_attack_actions()
+            .eq(game_state.iter_killing_attacks()));
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct SecondStarts;
+
+    impl StateSpace<2> for SecondStarts {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const STARTING_PLAYER: usize = 1;
+    }
+
+    #[test]
+    fn starting_player() {
+        let game_state = Chopsticks
+            .get_initial_state()
+            .with_starting_player(1)
+            .expect("valid");
+        assert_eq!(game_state.i, 1);
+        assert_eq!(game_state.get_abbreviation(), "1111");
+        assert!(matches!(
+            game_state.get_status(),
+            status::Status::Turn { i: 1 }
+        ));
+        assert!(game_state.iter_actions().all(|action| action.get_i() == 1));
+        assert!(matches!(
+            Chopsticks.get_initial_state().with_starting_player(2),
+            Err(ValueError::PlayerIndexOutOfBounds)
+        ));
+        let game_state = SecondStarts.get_initial_state();
+        assert_eq!(game_state.i, 1);
+        assert_eq!(game_state.get_abbreviation(), "1111");
+    }
+
+    #[test]
+    fn play_actions() {
+        let attack = |i, j, a, b| action::Action::Attack { i, j, a, b };
+        let mut game_state = Chopsticks.get_initial_state();
+        let actions = [attack(0, 1, 0, 1), attack(1, 0, 1, 1), attack(0, 1, 1, 1)];
+        assert!(game_state.play_actions(&actions).is_ok());
+        assert_eq!(game_state.get_abbreviation(), "1310");
+        assert_eq!(game_state.i, 1);
+
+        let mut game_state = Chopsticks.get_initial_state();
+        let before = game_state.clone();
+        let actions = [attack(0, 1, 0, 1), attack(1, 0, 1, 1), attack(1, 0, 1, 1)];
+        assert!(matches!(
+            game_state.play_actions(&actions),
+            Err((2, action::ActionError::WrongTurn))
+        ));
+        assert_eq!(game_state, before);
+        assert_eq!(game_state.i, 0);
+    }
+
+    #[test]
+    fn is_player_alive() {
+        let game_state = State::<2, Chopsticks>::from_abbreviation("0012").expect("valid");
+        assert_eq!(game_state.is_player_alive(0), Some(false));
+        assert_eq!(game_state.is_player_alive(1), Some(true));
+        assert_eq!(game_state.is_player_alive(2), None);
+    }
+
+    #[test]
+    fn material_scores() {
+        assert_eq!(Chopsticks.get_initial_state().material_scores(), [0, 0]);
+        assert_eq!(
+            State::<3, ThreePlayers>::default().material_scores(),
+            [0; 3]
+        );
+        let game_state =
+            State::<3, ThreePlayers>::with_hands([[1, 3], [2, 2], [1, 1]], 0).expect("valid");
+        assert_eq!(game_state.material_scores(), [1, 1, -2]);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("2401").expect("valid");
+        assert_eq!(game_state.material_scores(), [5, -5]);
+    }
+
+    #[test]
+    fn alive_count() {
+        use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game_state = State::<3, ThreePlayers>::default();
+        let mut history = Vec::new();
+        for _ in 0..300 {
+            let undo = !history.is_empty() && (game_state.is_terminal() || rng.gen_bool(0.3));
+            if undo {
+                let action = history.pop().expect("played action");
+                assert!(game_state.undo_action(&action).is_ok());
+            } else {
+                let action = game_state.iter_actions().choose(&mut rng).expect("action");
+                assert!(game_state.play_action(&action).is_ok());
+                history.push(action);
+            }
+            assert_eq!(game_state.alive, game_state.iter_player_indexes().count());
+        }
+        let game_state = State::<2, Chopsticks>::from_abbreviation("0012").expect("valid");
+        assert_eq!(game_state.alive, 1);
+    }
+
+    #[test]
+    fn hash() {
+        let game_state = State::<2, Chopsticks>::with_hands([[1, 3], [2, 2]], 0).expect("valid");
+        let mut states = HashSet::from([game_state.clone()]);
+        assert!(!states.insert(game_state.clone()));
+        let mut other_turn = game_state.clone();
+        other_turn.set_turn(1);
+        let swapped = State::<2, Chopsticks>::with_hands([[3, 1], [2, 2]], 0).expect("valid");
+        assert!(states.insert(other_turn));
+        assert!(states.insert(swapped));
+        assert_eq!(states.len(), 3);
+    }
+
+    #[test]
+    fn normalize_turn() {
+        let state_0 = State::<2, Chopsticks>::with_hands([[1, 4], [2, 3]], 0).expect("valid");
+        let state_1 = State::<2, Chopsticks>::with_hands([[2, 3], [1, 4]], 1).expect("valid");
+        assert_ne!(state_0, state_1);
+        assert_eq!(state_0.normalize_turn(), state_1.normalize_turn());
+        assert_eq!(state_0.normalize_turn(), state_0);
+    }
+
+    #[test]
+    fn simulate_random() {
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0);
+        let mut game_state = Chopsticks.get_initial_state();
+        for _ in 0..10 {
+            if let Some(i) = game_state.simulate_random(&mut rng) {
+                assert!(i < 2);
+            }
+            assert_eq!(game_state.i, 0);
+            assert_eq!(game_state.get_abbreviation(), "1111");
+        }
+    }
+
     #[test]
     fn short_game() {
         let mut game_state = Chopsticks.get_initial_state(); // 1111
+        assert_eq!(game_state.get_winner(), None);
         assert!(game_state.play_attack(0, 1, 0, 1).is_ok()); // 1112
         assert!(game_state.play_attack(1, 0, 1, 1).is_ok()); // 1312
         assert!(game_state.play_attack(0, 1, 1, 1).is_ok()); // 1310
@@ -418,5 +2751,40 @@ mod tests {
             game_state.get_status(),
             status::Status::Over { i: 0 }
         ));
+        assert_eq!(game_state.get_winner(), Some(0));
+    }
+
+    #[test]
+    fn advantage() {
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        assert_eq!(game_state.advantage(0), 1.0);
+        assert_eq!(game_state.advantage(1), -1.0);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1400").expect("valid");
+        assert_eq!(game_state.advantage(0), 1.0);
+        let game_state = State::<2, Chopsticks>::from_abbreviation("1213").expect("valid");
+        assert!(game_state.advantage(0) > 0.0);
+        let advantage = Chopsticks.get_initial_state().advantage(0);
+        assert!((-0.5..=0.5).contains(&advantage));
+    }
+
+    #[test]
+    fn status_text() {
+        let mut game_state = State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        assert_eq!(game_state.get_status_text(), "Turn: P0");
+        assert!(game_state.play_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(game_state.get_status_text(), "Winner: P0");
+        let mut game_state =
+            State::<2, MisereChopsticks>::from_abbreviation("1410").expect("valid");
+        assert!(game_state.play_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(game_state.get_status_text(), "Winner: P1");
+    }
+
+    #[test]
+    fn misere_winner() {
+        let mut game_state =
+            State::<2, MisereChopsticks>::from_abbreviation("1410").expect("valid");
+        assert_eq!(game_state.get_winner(), None);
+        assert!(game_state.play_attack(0, 1, 1, 0).is_ok());
+        assert_eq!(game_state.get_winner(), Some(1));
     }
 }
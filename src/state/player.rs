@@ -1,20 +1,40 @@
-use super::N_HANDS;
-use crate::state_space::StateSpace;
+use crate::state_space::{Elimination, StateSpace};
 use std::marker::PhantomData;
 
 /// The position for an individual player.
 #[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
 pub struct Player<const N: usize, T: StateSpace<N>> {
     /// A player's hands sorted in ascending order.
-    pub hands: [u32; N_HANDS],
+    #[cfg_attr(feature = "serde", serde(with = "crate::state::serde_hands"))]
+    pub hands: T::Hands,
 
+    #[cfg_attr(feature = "serde", serde(skip))]
     phantom: PhantomData<T>,
 }
 
 impl<const N: usize, T: StateSpace<N>> Player<N, T> {
-    /// Whether the player has been eliminated
+    /// Whether the player has been eliminated according to `StateSpace::ELIMINATION`
     pub fn is_eliminated(&self) -> bool {
-        self.hands.iter().all(|&hand| hand == 0)
+        let mut hands = self.hands.as_ref().iter();
+        match T::ELIMINATION {
+            Elimination::AllHandsDead => hands.all(|&hand| hand == 0),
+            Elimination::AnyHandDead => hands.any(|&hand| hand == 0),
+        }
+    }
+
+    /// Number of hands with fingers
+    pub fn alive_hand_count(&self) -> usize {
+        self.iter_alive_fingers_indexes().count()
+    }
+
+    /// Sum of the fingers on every hand
+    pub fn total_fingers(&self) -> u32 {
+        self.hands.as_ref().iter().sum()
     }
 
     /// Finger indices that are attackable
@@ -22,6 +42,7 @@ impl<const N: usize, T: StateSpace<N>> Player<N, T> {
         &self,
     ) -> impl Iterator<Item = usize> + std::clone::Clone + '_ {
         self.hands
+            .as_ref()
             .iter()
             .enumerate()
             .filter(|(_, &fingers)| fingers != 0)
@@ -32,8 +53,57 @@ impl<const N: usize, T: StateSpace<N>> Player<N, T> {
 impl<const N: usize, T: StateSpace<N>> Default for Player<N, T> {
     fn default() -> Player<N, T> {
         Player {
-            hands: [T::INITIAL_FINGERS; N_HANDS],
+            hands: T::get_initial_hands(),
             phantom: PhantomData {},
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{status::Status, State};
+    use crate::state_space::{chopsticks::Chopsticks, Elimination, StateSpace};
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct AnyHand;
+
+    impl StateSpace<2> for AnyHand {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const ELIMINATION: Elimination = Elimination::AnyHandDead;
+    }
+
+    #[test]
+    fn elimination() {
+        let state = State::<2, Chopsticks>::with_hands([[1, 4], [1, 0]], 0).expect("valid");
+        assert!(!state.get_players()[1].is_eliminated());
+        assert!(matches!(state.get_status(), Status::Turn { i: 0 }));
+        let state = State::<2, AnyHand>::with_hands([[1, 4], [1, 0]], 0).expect("valid");
+        assert!(!state.get_players()[0].is_eliminated());
+        assert!(state.get_players()[1].is_eliminated());
+        assert!(matches!(state.get_status(), Status::Over { i: 0 }));
+        let mut state = AnyHand.get_initial_state();
+        assert!(state.play_attack(0, 1, 0, 0).is_ok());
+        assert!(state.play_attack(1, 0, 0, 0).is_ok());
+        assert!(state.play_split(0, [3, 1], [2, 2]).is_ok());
+        assert!(state.play_attack(1, 0, 0, 0).is_ok());
+        assert_eq!(state.get_abbreviation(), "4221");
+        assert!(state.play_attack(0, 1, 0, 1).is_ok());
+        assert!(state.is_terminal());
+        assert_eq!(state.get_winner(), Some(0));
+    }
+
+    #[test]
+    fn alive_hands_and_fingers() {
+        let state = State::<2, Chopsticks>::with_hands([[2, 3], [0, 4]], 0).expect("valid");
+        assert_eq!(state.get_players()[0].alive_hand_count(), 2);
+        assert_eq!(state.get_players()[0].total_fingers(), 5);
+        assert_eq!(state.get_players()[1].alive_hand_count(), 1);
+        assert_eq!(state.get_players()[1].total_fingers(), 4);
+        let state = State::<2, Chopsticks>::with_hands([[1, 1], [0, 0]], 0).expect("valid");
+        assert!(state.get_players()[1].is_eliminated());
+        assert_eq!(state.get_players()[1].alive_hand_count(), 0);
+        assert_eq!(state.get_players()[1].total_fingers(), 0);
+    }
+}
@@ -3,7 +3,8 @@ pub enum Status {
     /// The player id of the player for the current turn
     Turn { i: usize },
 
-    /// The winner id after the game is over
+    /// The last player standing after the game is over. They win unless the `StateSpace` is
+    /// misère.
     Over { i: usize },
 }
 
@@ -17,6 +18,17 @@ impl Status {
     }
 }
 
+/// "Turn: P2" while the game is ongoing or "Winner: P0" for the last player standing, who lost if
+/// the `StateSpace` is misère. `State::get_status_text` names the actual winner.
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Turn { i } => write!(f, "Turn: P{i}"),
+            Status::Over { i } => write!(f, "Winner: P{i}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,4 +46,10 @@ mod tests {
         let status = Status::Over { i };
         assert_eq!(status.get_i(), i);
     }
+
+    #[test]
+    fn display() {
+        assert_eq!(Status::Turn { i: 2 }.to_string(), "Turn: P2");
+        assert_eq!(Status::Over { i: 0 }.to_string(), "Winner: P0");
+    }
 }
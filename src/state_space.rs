@@ -1,35 +1,211 @@
 use crate::state;
 
-/// Number of hands per player. Currently not extensible because of the complexity required to do
-/// so.
-const N_HANDS: usize = 2;
+/// Fingers on each of a player's hands, implemented for `[u32; LEN]`. Fingers are always `u32`
+/// like `ROLLOVER` and every serial so arithmetic between them needs no conversions. Fingers are
+/// below `ROLLOVER` so large simulations can store a `State` compactly by its `serialize_state`
+/// rather than by a smaller finger type.
+pub trait Hands:
+    Copy
+    + Eq
+    + std::fmt::Debug
+    + Default
+    + AsRef<[u32]>
+    + AsMut<[u32]>
+    + std::ops::Index<usize, Output = u32>
+    + std::ops::IndexMut<usize>
+{
+    /// Number of hands
+    const LEN: usize;
+}
+
+impl<const LEN: usize> Hands for [u32; LEN]
+where
+    [u32; LEN]: Default,
+{
+    const LEN: usize = LEN;
+}
+
+/// How an attack that brings a hand to `ROLLOVER` or beyond resolves
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RolloverMode {
+    /// Hands wrap around mod `ROLLOVER`
+    Modulo,
+
+    /// Hands reaching exactly `FINGERS` are killed and attacks exceeding it are illegal, which
+    /// includes the combined attacks on a hand in `State::play_simultaneous`. A player with no
+    /// legal attack or split must pass.
+    Cutoff,
+}
+
+/// When a player is out of the game
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Elimination {
+    /// Once every hand is dead
+    AllHandsDead,
+
+    /// As soon as any hand is dead
+    AnyHandDead,
+}
 
 pub trait StateSpace<const N: usize>: Sized + Copy {
     /// Number of players for a game
     const N_PLAYERS: usize = N;
 
+    /// Fingers on each hand of a player, typically `[u32; 2]`
+    type Hands: Hands;
+
     /// Number of hands per player
-    const N_HANDS: usize = N_HANDS;
+    const N_HANDS: usize = <Self::Hands as Hands>::LEN;
 
     /// A hand is killed when its value is 0 mod `ROLLOVER`
     const ROLLOVER: u32;
 
+    /// Rollover of each hand, no more than `ROLLOVER`, where hands differ such as a thumb
+    const ROLLOVERS: Option<Self::Hands> = None;
+
+    /// Fingers on a full hand, killed when an attack reaches exactly `FINGERS`
+    const FINGERS: u32 = Self::ROLLOVER;
+
+    /// Fingers taken from a hand beyond `FINGERS`, either `FINGERS - 1` or `FINGERS`
+    const ROLLOVER_ADD: u32 = Self::FINGERS;
+
+    /// How attacks resolve at `ROLLOVER`
+    const ROLLOVER_MODE: RolloverMode = RolloverMode::Modulo;
+
     /// Hands are initialized with this number of fingers
     const INITIAL_FINGERS: u32;
 
+    /// Whether the last player standing loses rather than wins
+    const MISERE: bool = false;
+
+    /// Whether a player may attack one of their own hands with another
+    const ALLOW_SELF_ATTACK: bool = false;
+
+    /// Whether a split may leave a hand with no fingers, reviving it later by another split
+    const ALLOW_REVIVE: bool = false;
+
+    /// Whether a lone player keeps taking turns rather than the game being over, for single
+    /// player puzzles and analysis
+    const ALLOW_SOLO: bool = false;
+
+    /// Whether a player may pass with `Action::Pass` rather than attack or split. Repetitions
+    /// still draw games so passing back and forth ends them.
+    const ALLOW_PASS: bool = false;
+
+    /// Whether an `Action::Pass` can ever be played, by choice or forced by `RolloverMode::Cutoff`
+    const HAS_PASS: bool = Self::ALLOW_PASS || matches!(Self::ROLLOVER_MODE, RolloverMode::Cutoff);
+
+    /// Player to move first from `get_initial_state`
+    const STARTING_PLAYER: usize = 0;
+
+    /// Whether turns pass to the next higher player index rather than the next lower one
+    const CLOCKWISE: bool = true;
+
+    /// When a player is eliminated
+    const ELIMINATION: Elimination = Elimination::AllHandsDead;
+
     /// The base used for a `Split` `Action` and `Player` state serialization
-    const PLAYER_SERIAL_BASE: u32 = Self::ROLLOVER.pow(N_HANDS as u32);
+    const PLAYER_SERIAL_BASE: u32 = Self::ROLLOVER.pow(Self::N_HANDS as u32);
 
     /// The base used for an `Attack` `Action`. `N_PLAYERS` is 1 higher than what is necessary
-    /// because a player cannot attack index 0 which is their own index.
-    const ATTACK_SERIAL_BASE: u32 = (Self::N_PLAYERS * N_HANDS * N_HANDS) as u32;
+    /// unless `ALLOW_SELF_ATTACK` because otherwise a player cannot attack index 0 which is
+    /// their own index.
+    const ATTACK_SERIAL_BASE: u32 = (Self::N_PLAYERS * Self::N_HANDS * Self::N_HANDS) as u32;
+
+    /// Upper bound on the number of `Action`s from any `State`. There are `N_HANDS * N_HANDS`
+    /// attacks against each opponent, `N_HANDS * (N_HANDS - 1)` against themselves if
+    /// `ALLOW_SELF_ATTACK`, at most `max_splits` ways to split any total and a pass if
+    /// `HAS_PASS`.
+    const MAX_ACTIONS: usize = (Self::N_PLAYERS - 1) * Self::N_HANDS * Self::N_HANDS
+        + Self::ALLOW_SELF_ATTACK as usize * Self::N_HANDS * (Self::N_HANDS - 1)
+        + if Self::ROLLOVERS.is_some() {
+            // The other hands determine the last so this bounds every arrangement
+            Self::ROLLOVER.pow(Self::N_HANDS as u32 - 1) as usize
+        } else {
+            max_splits(Self::N_HANDS, !Self::ALLOW_REVIVE as u32, Self::ROLLOVER)
+        }
+        + Self::HAS_PASS as usize;
+
+    /// Statically check the base used for an `Action` which may be a `Split`, an `Attack` or a
+    /// `Pass` against u32
+    const ACTION_SERIAL_BASE: u32 =
+        Self::PLAYER_SERIAL_BASE + Self::ATTACK_SERIAL_BASE + Self::HAS_PASS as u32;
+
+    /// Statically check `State` serial base, including the current turn, against u32
+    const STATE_SERIAL_BASE: u32 =
+        Self::PLAYER_SERIAL_BASE.pow(Self::N_PLAYERS as u32) * Self::N_PLAYERS as u32;
+
+    /// Rollover of hand `h` from `ROLLOVERS` or `ROLLOVER`
+    fn get_rollover(h: usize) -> u32 {
+        Self::ROLLOVERS.map_or(Self::ROLLOVER, |rollovers| rollovers[h])
+    }
+
+    /// Fingers on a full hand `h` from `ROLLOVERS` or `FINGERS`
+    fn get_fingers(h: usize) -> u32 {
+        Self::ROLLOVERS.map_or(Self::FINGERS, |rollovers| rollovers[h])
+    }
+
+    /// Fingers of hand `b` with `defender` fingers after an `attacker` attacks it
+    fn resolve_attack(defender: u32, attacker: u32, b: usize) -> u32 {
+        let mut total = defender + attacker;
+        if Self::ROLLOVERS.is_some() {
+            return total % Self::get_rollover(b);
+        }
+        while total > Self::FINGERS {
+            total -= Self::ROLLOVER_ADD;
+        }
+        if total == Self::FINGERS {
+            0
+        } else {
+            total
+        }
+    }
+
+    /// Inverse of `resolve_attack` or `None` if no alive hand could have become `fingers`
+    fn unresolve_attack(fingers: u32, attacker: u32, b: usize) -> Option<u32> {
+        if Self::ROLLOVERS.is_some() {
+            let rollover = Self::get_rollover(b);
+            let before = (fingers + rollover - attacker % rollover) % rollover;
+            return (fingers < rollover && before != 0).then_some(before);
+        }
+        let before = if fingers == 0 {
+            Self::FINGERS.checked_sub(attacker)?
+        } else if fingers > attacker {
+            fingers - attacker
+        } else {
+            (fingers + Self::ROLLOVER_ADD).checked_sub(attacker)?
+        };
+        (1..Self::FINGERS).contains(&before).then_some(before)
+    }
 
-    /// Statically check the base used for an `Action` which may be a `Split` or an `Attack`
-    /// against u32
-    const ACTION_SERIAL_BASE: u32 = Self::PLAYER_SERIAL_BASE + Self::ATTACK_SERIAL_BASE;
+    /// Whether every `State` and `Action` serial fits in `U`. Bases are computed without the
+    /// `STATE_SERIAL_BASE` and `ACTION_SERIAL_BASE` constants which fail to compile on overflow.
+    fn check_serial_size<U: num_traits::PrimInt>() -> Result<(), state::ValueError> {
+        let rollover = Self::ROLLOVER as u128;
+        let player_base = rollover.checked_pow(Self::N_HANDS as u32);
+        let state_base = player_base
+            .and_then(|base| base.checked_pow(Self::N_PLAYERS as u32))
+            .and_then(|base| base.checked_mul(Self::N_PLAYERS as u128));
+        let action_base = player_base
+            .map(|base| base + Self::ATTACK_SERIAL_BASE as u128 + Self::HAS_PASS as u128);
+        let max = U::max_value().to_u128().expect("unsigned max");
+        match (state_base, action_base) {
+            (Some(state_base), Some(action_base))
+                if state_base - 1 <= max && action_base - 1 <= max =>
+            {
+                Ok(())
+            }
+            _ => Err(state::ValueError::SerialOverflow),
+        }
+    }
 
-    /// Statically check `State` serial base against u32
-    const STATE_SERIAL_BASE: u32 = Self::PLAYER_SERIAL_BASE.pow(Self::N_PLAYERS as u32);
+    /// Each player's hands at the start of a game, `INITIAL_FINGERS` on every hand unless
+    /// overridden for handicaps or puzzles
+    fn get_initial_hands() -> Self::Hands {
+        let mut hands = Self::Hands::default();
+        hands.as_mut().fill(Self::INITIAL_FINGERS);
+        hands
+    }
 
     /// Generate a new chopsticks game instance
     fn get_initial_state(&self) -> state::State<N, Self>
@@ -38,6 +214,139 @@ pub trait StateSpace<const N: usize>: Sized + Copy {
     {
         state::State::default()
     }
+
+    /// Number of syntactically distinct `State`s, every hand below its rollover for every
+    /// player and turn, whether or not they can occur in a game. `None` if it overflows `u32`.
+    fn state_space_size(&self) -> Option<u32> {
+        let player_size = (0..Self::N_HANDS)
+            .map(Self::get_rollover)
+            .try_fold(1u32, u32::checked_mul)?;
+        player_size
+            .checked_pow(Self::N_PLAYERS as u32)?
+            .checked_mul(Self::N_PLAYERS as u32)
+    }
+
+    /// Number of distinct `State`s reached by `State::iter_reachable` from the initial state
+    fn reachable_state_count(&self) -> usize
+    where
+        Self: std::fmt::Debug,
+    {
+        self.get_initial_state().iter_reachable().count()
+    }
+
+    /// Unique serial in `0..STATE_SERIAL_BASE` for a `State`. The current turn is the least
+    /// significant digit in base `N_PLAYERS` followed by each hand in base `ROLLOVER`.
+    fn serialize_state(state: &state::State<N, Self>) -> u32 {
+        state
+            .get_players()
+            .iter()
+            .rev()
+            .flat_map(|player| player.hands.as_ref().iter().rev())
+            .fold(0, |serial, hand| serial * Self::ROLLOVER + hand)
+            * Self::N_PLAYERS as u32
+            + state.get_i() as u32
+    }
+
+    /// Inverse of `serialize_state`
+    fn deserialize_state(serial: u32) -> Result<state::State<N, Self>, state::ValueError> {
+        if serial >= Self::STATE_SERIAL_BASE {
+            return Err(state::ValueError::SerialOutOfBounds);
+        }
+        let i = (serial % Self::N_PLAYERS as u32) as usize;
+        let mut serial = serial / Self::N_PLAYERS as u32;
+        let players = std::array::from_fn(|_| {
+            let mut player = state::player::Player::<N, Self>::default();
+            for hand in player.hands.as_mut().iter_mut() {
+                *hand = serial % Self::ROLLOVER;
+                serial /= Self::ROLLOVER;
+            }
+            player
+        });
+        Ok(state::State::from_players(i, players))
+    }
+
+    /// Unique serial in `0..ACTION_SERIAL_BASE` for an `Action` relative to its acting player.
+    /// An `Attack` is `(j_offset * N_HANDS + a) * N_HANDS + b` where `j_offset` is how many
+    /// players after `i` the defender `j` is. A `Split` is `ATTACK_SERIAL_BASE` plus the serial
+    /// of `hands_1` with hand 0 as the least significant digit in base `ROLLOVER`. A `Pass` is the
+    /// last serial.
+    fn serialize_action(action: &state::action::Action<N, Self>) -> u32 {
+        match action {
+            state::action::Action::Attack { i, j, a, b } => {
+                let j_offset = (j + Self::N_PLAYERS - i) % Self::N_PLAYERS;
+                ((j_offset * Self::N_HANDS + a) * Self::N_HANDS + b) as u32
+            }
+            state::action::Action::Split { hands_1, .. } => {
+                Self::ATTACK_SERIAL_BASE
+                    + hands_1
+                        .as_ref()
+                        .iter()
+                        .rev()
+                        .fold(0, |serial, hand| serial * Self::ROLLOVER + hand)
+            }
+            state::action::Action::Pass { .. } => Self::ACTION_SERIAL_BASE - 1,
+            _ => panic!("expect not phantom"),
+        }
+    }
+
+    /// Inverse of `serialize_action` for the current player of `state`
+    fn deserialize_action(
+        state: &state::State<N, Self>,
+        serial: u32,
+    ) -> Result<state::action::Action<N, Self>, state::ValueError> {
+        let i = state.get_i();
+        if serial < Self::ATTACK_SERIAL_BASE {
+            let serial = serial as usize;
+            let b = serial % Self::N_HANDS;
+            let a = serial / Self::N_HANDS % Self::N_HANDS;
+            let j = (i + serial / Self::N_HANDS / Self::N_HANDS) % Self::N_PLAYERS;
+            Ok(state::action::Action::Attack { i, j, a, b })
+        } else if Self::HAS_PASS && serial == Self::ACTION_SERIAL_BASE - 1 {
+            Ok(state::action::Action::Pass { i })
+        } else if serial < Self::ACTION_SERIAL_BASE {
+            let mut serial = serial - Self::ATTACK_SERIAL_BASE;
+            let mut hands_1 = Self::Hands::default();
+            for hand in hands_1.as_mut().iter_mut() {
+                *hand = serial % Self::ROLLOVER;
+                serial /= Self::ROLLOVER;
+            }
+            Ok(state::action::Action::Split {
+                i,
+                hands_0: state.get_players()[i].hands,
+                hands_1,
+            })
+        } else {
+            Err(state::ValueError::SerialOutOfBounds)
+        }
+    }
+}
+
+/// Most ways to split any total among `n_hands` hands with values in `min..rollover`
+const fn max_splits(n_hands: usize, min: u32, rollover: u32) -> usize {
+    let mut max = 0;
+    let mut total = 0;
+    while total <= n_hands as u32 * rollover {
+        let count = count_splits(total, n_hands, min, rollover);
+        if count > max {
+            max = count;
+        }
+        total += 1;
+    }
+    max
+}
+
+/// Ways to split `total` among `n_hands` ascending hands with values in `min..rollover`
+pub(crate) const fn count_splits(total: u32, n_hands: usize, min: u32, rollover: u32) -> usize {
+    if n_hands == 0 {
+        return (total == 0) as usize;
+    }
+    let mut count = 0;
+    let mut hand = min;
+    while hand < rollover && hand * n_hands as u32 <= total {
+        count += count_splits(total - hand, n_hands - 1, hand, rollover);
+        hand += 1;
+    }
+    count
 }
 
 pub mod chopsticks {
@@ -47,7 +356,248 @@ pub mod chopsticks {
     pub struct Chopsticks;
 
     impl StateSpace<2> for Chopsticks {
+        type Hands = [u32; 2];
         const ROLLOVER: u32 = 5;
         const INITIAL_FINGERS: u32 = 1;
     }
 }
+
+pub mod cutoff_chopsticks {
+    use super::*;
+
+    /// Two player chopsticks where attacks cannot exceed `ROLLOVER`
+    #[derive(Copy, Clone, Debug, PartialEq, Default)]
+    pub struct CutoffChopsticks;
+
+    impl StateSpace<2> for CutoffChopsticks {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const ROLLOVER_MODE: RolloverMode = RolloverMode::Cutoff;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+}
+
+pub mod misere_chopsticks {
+    use super::*;
+
+    /// Two player chopsticks where the last player standing loses
+    #[derive(Copy, Clone, Debug, PartialEq, Default)]
+    pub struct MisereChopsticks;
+
+    impl StateSpace<2> for MisereChopsticks {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+        const MISERE: bool = true;
+    }
+}
+
+pub mod handicap_chopsticks {
+    use super::*;
+
+    /// Two player chopsticks where each player starts with one and two fingers
+    #[derive(Copy, Clone, Debug, PartialEq, Default)]
+    pub struct HandicapChopsticks;
+
+    impl StateSpace<2> for HandicapChopsticks {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+
+        fn get_initial_hands() -> [u32; 2] {
+            [1, 2]
+        }
+    }
+}
+
+pub mod three_handed {
+    use super::*;
+
+    /// Two player chopsticks where each player has three hands
+    #[derive(Copy, Clone, Debug, PartialEq, Default)]
+    pub struct ThreeHanded;
+
+    impl StateSpace<2> for ThreeHanded {
+        type Hands = [u32; 3];
+        const ROLLOVER: u32 = 5;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chopsticks::Chopsticks;
+    use super::*;
+    use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+    use std::collections::HashSet;
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct ThreePlayers;
+
+    impl StateSpace<3> for ThreePlayers {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 4;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct Rollover7;
+
+    impl StateSpace<2> for Rollover7 {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 7;
+        const INITIAL_FINGERS: u32 = 2;
+    }
+
+    /// Check serialization round trips along random games
+    fn state_round_trip<const N: usize, T: StateSpace<N> + std::fmt::Debug + PartialEq>(
+        state_space: T,
+    ) {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let mut state = state_space.get_initial_state();
+            for _ in 0..50 {
+                let serial = T::serialize_state(&state);
+                assert!(serial < T::STATE_SERIAL_BASE);
+                assert_eq!(T::deserialize_state(serial).expect("in bounds"), state);
+                for action in state.iter_actions() {
+                    let serial = T::serialize_action(&action);
+                    assert!(serial < T::ACTION_SERIAL_BASE);
+                    let deserialized = T::deserialize_action(&state, serial).expect("in bounds");
+                    assert_eq!(deserialized, action);
+                }
+                let Some(action) = state.iter_actions().choose(&mut rng) else {
+                    break;
+                };
+                if state.play_action(&action).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Check every serial round trips
+    fn serial_round_trip<const N: usize, T: StateSpace<N>>() {
+        for serial in 0..T::STATE_SERIAL_BASE {
+            let state = T::deserialize_state(serial).expect("in bounds");
+            assert_eq!(T::serialize_state(&state), serial);
+        }
+        assert!(T::deserialize_state(T::STATE_SERIAL_BASE).is_err());
+    }
+
+    /// Check every action serial round trips
+    fn action_serial_round_trip<const N: usize, T: StateSpace<N> + std::fmt::Debug>(
+        state_space: T,
+    ) {
+        let state = state_space.get_initial_state();
+        for serial in 0..T::ACTION_SERIAL_BASE {
+            let action = T::deserialize_action(&state, serial).expect("in bounds");
+            assert_eq!(T::serialize_action(&action), serial);
+        }
+        assert!(T::deserialize_action(&state, T::ACTION_SERIAL_BASE).is_err());
+    }
+
+    #[test]
+    fn chopsticks_serialization() {
+        state_round_trip(Chopsticks);
+        serial_round_trip::<2, Chopsticks>();
+        action_serial_round_trip(Chopsticks);
+    }
+
+    #[test]
+    fn three_players_serialization() {
+        state_round_trip(ThreePlayers);
+        serial_round_trip::<3, ThreePlayers>();
+        action_serial_round_trip(ThreePlayers);
+    }
+
+    #[test]
+    fn rollover_7_serialization() {
+        state_round_trip(Rollover7);
+        serial_round_trip::<2, Rollover7>();
+        action_serial_round_trip(Rollover7);
+    }
+
+    /// Check no reachable `State` has more than `MAX_ACTIONS` actions
+    fn max_actions<const N: usize, T: StateSpace<N> + std::fmt::Debug>(state_space: T) {
+        let initial_state = state_space.get_initial_state();
+        let mut seen = HashSet::from([(initial_state.get_i(), initial_state.get_abbreviation())]);
+        let mut queue = vec![initial_state];
+        while let Some(state) = queue.pop() {
+            let actions: Vec<_> = state.iter_actions().collect();
+            assert!(actions.len() <= T::MAX_ACTIONS);
+            for action in actions {
+                let mut next_state = state.clone();
+                if next_state.play_action(&action).is_ok()
+                    && seen.insert((next_state.get_i(), next_state.get_abbreviation()))
+                {
+                    queue.push(next_state);
+                }
+            }
+        }
+        assert!(seen.len() > 1);
+    }
+
+    #[test]
+    fn chopsticks_max_actions() {
+        max_actions(Chopsticks);
+    }
+
+    #[test]
+    fn three_handed_max_actions() {
+        max_actions(three_handed::ThreeHanded);
+    }
+
+    #[test]
+    fn three_handed_serialization() {
+        state_round_trip(three_handed::ThreeHanded);
+        serial_round_trip::<2, three_handed::ThreeHanded>();
+        action_serial_round_trip(three_handed::ThreeHanded);
+    }
+
+    #[test]
+    fn state_space_size() {
+        assert_eq!(
+            Chopsticks.state_space_size(),
+            Some(Chopsticks::STATE_SERIAL_BASE)
+        );
+        assert_eq!(ThreePlayers.state_space_size(), Some(16 * 16 * 16 * 3));
+        assert_eq!(TenPlayers.state_space_size(), None);
+        let reachable = Chopsticks.reachable_state_count();
+        assert!(reachable < Chopsticks.state_space_size().expect("fits") as usize);
+        assert_eq!(reachable, Chopsticks.reachable_state_count());
+        assert_eq!(reachable, crate::solver::solve(&Chopsticks).len());
+    }
+
+    #[test]
+    fn handicap_initial_state() {
+        let state = handicap_chopsticks::HandicapChopsticks.get_initial_state();
+        assert_eq!(state.get_abbreviation(), "1212");
+        state_round_trip(handicap_chopsticks::HandicapChopsticks);
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct TenPlayers;
+
+    impl StateSpace<10> for TenPlayers {
+        type Hands = [u32; 2];
+        const ROLLOVER: u32 = 10;
+        const INITIAL_FINGERS: u32 = 1;
+    }
+
+    #[test]
+    fn check_serial_size() {
+        assert!(Chopsticks::check_serial_size::<u32>().is_ok());
+        assert!(Chopsticks::check_serial_size::<u16>().is_ok());
+        assert!(matches!(
+            Chopsticks::check_serial_size::<u8>(),
+            Err(state::ValueError::SerialOverflow)
+        ));
+        assert!(matches!(
+            TenPlayers::check_serial_size::<u32>(),
+            Err(state::ValueError::SerialOverflow)
+        ));
+        assert!(TenPlayers::check_serial_size::<u64>().is_err());
+        assert!(TenPlayers::check_serial_size::<u128>().is_ok());
+    }
+}
@@ -42,6 +42,7 @@ impl<const N: usize, T: state_space::StateSpace<N>> CommandPrompt<N, T> {
         match move_buffer.as_str().trim() {
             "attack" => self.attack_prompt(gamestate),
             "split" => self.split_prompt(gamestate),
+            "pass" if T::HAS_PASS => Ok(state::action::Action::Pass { i }),
             _ => Err(PromptError("action")),
         }
     }
@@ -52,7 +53,7 @@ impl<const N: usize, T: state_space::StateSpace<N>> CommandPrompt<N, T> {
         gamestate: &state::State<N, T>,
     ) -> Result<state::action::Action<N, T>, PromptError> {
         let i = gamestate.get_status().get_i();
-        let j = if gamestate.players.len() > 2 {
+        let j = if gamestate.get_players().len() > 2 {
             println!("Player {i}, what is the index of the player you are attacking?");
             read_parsable()?
         } else {
@@ -76,14 +77,15 @@ impl<const N: usize, T: state_space::StateSpace<N>> CommandPrompt<N, T> {
         gamestate: &state::State<N, T>,
     ) -> Result<state::action::Action<N, T>, PromptError> {
         let i = gamestate.get_status().get_i();
-        println!("Player {i}, how many fingers will you split for your left hand?");
-        let left = read_parsable()?;
-        println!("Player {i}, how many fingers will you split for your right hand?");
-        let right = read_parsable()?;
+        let mut hands_1 = T::Hands::default();
+        for (h, hand) in hands_1.as_mut().iter_mut().enumerate() {
+            println!("Player {i}, how many fingers will you split for hand {h}?");
+            *hand = read_parsable()?;
+        }
         Ok(state::action::Action::Split {
             i,
-            hands_0: gamestate.players[i].hands,
-            hands_1: [left, right],
+            hands_0: gamestate.get_players()[i].hands,
+            hands_1,
         })
     }
 }
@@ -0,0 +1,70 @@
+use super::*;
+use crate::{state, state_space};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Random action with probability `epsilon` and otherwise the action of the `inner` strategy
+#[derive(Clone)]
+pub struct EpsilonGreedy<S> {
+    pub inner: S,
+
+    /// Probability in `0.0..=1.0` of exploring with a random action
+    pub epsilon: f64,
+
+    rng: StdRng,
+}
+
+impl<S> EpsilonGreedy<S> {
+    /// Explores from entropy or reproducibly from `seed`
+    pub fn new(inner: S, epsilon: f64, seed: Option<u64>) -> EpsilonGreedy<S> {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        EpsilonGreedy {
+            inner,
+            epsilon,
+            rng,
+        }
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>, S: Strategy<N, T>> Strategy<N, T>
+    for EpsilonGreedy<S>
+{
+    fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+        if self.rng.gen::<f64>() < self.epsilon {
+            let n = self.rng.gen_range(0..state.count_actions());
+            state.nth_action(n).expect("multiple actions")
+        } else {
+            self.inner.get_action(state)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn zero_epsilon() {
+        let mut inner = random::Random::seeded(1);
+        let mut epsilon_greedy = EpsilonGreedy::new(random::Random::seeded(1), 0.0, Some(0));
+        let mut state = Chopsticks.get_initial_state();
+        while !state.is_terminal() {
+            let action = epsilon_greedy.get_action(&state);
+            assert_eq!(action, inner.get_action(&state));
+            assert!(state.play_action(&action).is_ok());
+        }
+    }
+
+    #[test]
+    fn one_epsilon() {
+        let mut epsilon_greedy = EpsilonGreedy::new(heuristic::Heuristic::default(), 1.0, Some(0));
+        let state = Chopsticks.get_initial_state();
+        for _ in 0..10 {
+            let action = epsilon_greedy.get_action(&state);
+            assert!(state.iter_actions().any(|legal| legal == action));
+        }
+    }
+}
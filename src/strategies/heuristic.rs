@@ -0,0 +1,102 @@
+use super::*;
+use crate::{state, state_space};
+use std::cmp::Reverse;
+
+/// Best action according to an evaluation of each resulting `State` for the current player.
+/// Ties go to the first action.
+#[derive(Clone)]
+pub struct Heuristic<const N: usize, T: state_space::StateSpace<N>> {
+    /// Scores a `State` for player `i`
+    evaluate: fn(&state::State<N, T>, usize) -> i32,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for Heuristic<N, T> {
+    fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+        let i = match state.get_status() {
+            state::status::Status::Turn { i } => i,
+            state::status::Status::Over { i: _ } => panic!("game is over"),
+        };
+        let mut state = state.clone();
+        let actions: Vec<_> = state.iter_actions().collect();
+        actions
+            .into_iter()
+            .min_by_key(|action| {
+                state.play_action(action).expect("valid action");
+                let score = (self.evaluate)(&state, i);
+                state.undo_action(action).expect("valid action");
+                Reverse(score)
+            })
+            .expect("multiple actions")
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Heuristic<N, T> {
+    pub fn new(evaluate: fn(&state::State<N, T>, usize) -> i32) -> Heuristic<N, T> {
+        Heuristic { evaluate }
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Default for Heuristic<N, T> {
+    fn default() -> Heuristic<N, T> {
+        Heuristic::new(material)
+    }
+}
+
+/// Alive hands then total fingers of player `i` less those of every opponent
+pub fn material<const N: usize, T: state_space::StateSpace<N>>(
+    state: &state::State<N, T>,
+    i: usize,
+) -> i32 {
+    let hand_weight = (T::N_HANDS as u32 * T::ROLLOVER) as i32;
+    state
+        .get_players()
+        .iter()
+        .enumerate()
+        .map(|(j, player)| {
+            let alive = player.alive_hand_count() as i32;
+            let score = alive * hand_weight + player.total_fingers() as i32;
+            if i == j {
+                score
+            } else {
+                -score
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::chopsticks::Chopsticks;
+
+    #[test]
+    fn prefers_kill() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1314").expect("valid");
+        let action = Heuristic::default().get_action(&state);
+        assert_eq!(
+            action,
+            state::action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 0,
+                b: 1
+            }
+        );
+    }
+
+    #[test]
+    fn custom_evaluation() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1314").expect("valid");
+        let mut heuristic = Heuristic::new(|state, i| -material(state, i));
+        let action = heuristic.get_action(&state);
+        assert_eq!(
+            action,
+            state::action::Action::Attack {
+                i: 0,
+                j: 1,
+                a: 1,
+                b: 0
+            }
+        );
+    }
+}
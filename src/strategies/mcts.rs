@@ -0,0 +1,200 @@
+use super::*;
+use crate::{state, state_space};
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// Most visited action of a Monte Carlo tree search with `iterations` random rollouts. Actions
+/// are selected by UCT with exploration constant `c` and nodes are keyed by `serialize_state`
+/// so transpositions share statistics.
+#[derive(Clone)]
+pub struct Mcts {
+    /// Number of selection, expansion, rollout, and backpropagation cycles for each move
+    iterations: usize,
+
+    /// Weight of exploring rarely visited actions over exploiting valuable ones
+    c: f64,
+
+    rng: StdRng,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for Mcts {
+    fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+        if state.is_terminal() {
+            panic!("game is over");
+        }
+        let serial = T::serialize_state(state);
+        let mut tree = HashMap::from([(serial, Node::new(state))]);
+        for _ in 0..self.iterations {
+            self.iterate(&mut tree, state.clone());
+        }
+        let root = &tree[&serial];
+        root.children
+            .iter()
+            .max_by_key(|child| child.visits)
+            .expect("multiple actions")
+            .action
+    }
+}
+
+impl Mcts {
+    pub fn new(iterations: usize, c: f64) -> Mcts {
+        Mcts {
+            iterations,
+            c,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Reproducible rollouts
+    pub fn seeded(iterations: usize, c: f64, seed: u64) -> Mcts {
+        Mcts {
+            iterations,
+            c,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Selects down `tree` from `state` until a new or finished position, expands and simulates
+    /// it, then credits the result to every selected action
+    fn iterate<const N: usize, T: state_space::StateSpace<N>>(
+        &mut self,
+        tree: &mut HashMap<u32, Node<N, T>>,
+        mut state: state::State<N, T>,
+    ) {
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        let winner = loop {
+            if state.is_terminal() {
+                break state.get_winner();
+            }
+            let serial = T::serialize_state(&state);
+            if !seen.insert(serial) {
+                // Repeating a position on the path is treated as a draw
+                break None;
+            }
+            let Some(node) = tree.get(&serial) else {
+                tree.insert(serial, Node::new(&state));
+                let last = state.simulate_random(&mut self.rng);
+                break get_rollout_winner::<N, T>(last);
+            };
+            let k = node.select(self.c);
+            let action = node.children[k].action;
+            path.push((serial, k, state.get_i()));
+            state.play_action(&action).expect("valid action");
+        };
+        for (serial, k, i) in path {
+            let node = tree.get_mut(&serial).expect("visited node");
+            node.visits += 1;
+            let child = &mut node.children[k];
+            child.visits += 1;
+            child.value += match winner {
+                Some(j) if i == j => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+        }
+    }
+}
+
+/// Statistics of a position for the player to move
+struct Node<const N: usize, T: state_space::StateSpace<N>> {
+    visits: u32,
+    children: Vec<Child<N, T>>,
+}
+
+/// Statistics of an action from a `Node`
+struct Child<const N: usize, T: state_space::StateSpace<N>> {
+    action: state::action::Action<N, T>,
+    visits: u32,
+
+    /// Sum of rewards in `0.0..=1.0` for the player who made the action
+    value: f64,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Node<N, T> {
+    fn new(state: &state::State<N, T>) -> Node<N, T> {
+        Node {
+            visits: 0,
+            children: state
+                .iter_actions()
+                .map(|action| Child {
+                    action,
+                    visits: 0,
+                    value: 0.0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Index of the first unvisited child or else the child with the greatest UCT score
+    fn select(&self, c: f64) -> usize {
+        if let Some(k) = self.children.iter().position(|child| child.visits == 0) {
+            return k;
+        }
+        let ln_visits = (self.visits as f64).ln();
+        let uct = |child: &Child<N, T>| {
+            let visits = child.visits as f64;
+            child.value / visits + c * (ln_visits / visits).sqrt()
+        };
+        (0..self.children.len())
+            .max_by(|&a, &b| uct(&self.children[a]).total_cmp(&uct(&self.children[b])))
+            .expect("multiple actions")
+    }
+}
+
+/// Winner given the last player standing of `simulate_random` like `State::get_winner`
+fn get_rollout_winner<const N: usize, T: state_space::StateSpace<N>>(
+    last: Option<usize>,
+) -> Option<usize> {
+    match last {
+        Some(i) if !T::MISERE => Some(i),
+        Some(i) if N == 2 => Some(1 - i),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{multi_strategy::MultiStrategy, Game};
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn beats_random() {
+        let n_games = 10;
+        let mut wins = 0;
+        for g in 0..n_games {
+            let seat = g % 2;
+            let mut strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+                Box::new(random::Random::seeded(g as u64)),
+                Box::new(random::Random::seeded(g as u64)),
+            ];
+            strategies[seat] = Box::new(Mcts::seeded(200, std::f64::consts::SQRT_2, g as u64));
+            let mut game = MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+            if game.get_rankings()[seat] == 1 {
+                wins += 1;
+            }
+        }
+        assert!(wins > n_games / 2, "won {wins} of {n_games}");
+    }
+
+    #[test]
+    fn finds_win() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        let winning_action = state::action::Action::Attack {
+            i: 0,
+            j: 1,
+            a: 1,
+            b: 0,
+        };
+        let mut mcts = Mcts::seeded(100, std::f64::consts::SQRT_2, 0);
+        assert_eq!(mcts.get_action(&state), winning_action);
+    }
+
+    #[test]
+    fn no_iterations() {
+        let state = Chopsticks.get_initial_state();
+        let mut mcts = Mcts::seeded(0, std::f64::consts::SQRT_2, 0);
+        assert!(state.is_legal(&mcts.get_action(&state)));
+    }
+}
@@ -0,0 +1,154 @@
+use super::*;
+use crate::{state, state_space};
+
+/// Score of a won game for the searched player, less the plies it took to win
+const WIN: i32 = 1_000_000;
+
+/// Best action according to a `max_depth` ply alpha-beta search where every opponent plays
+/// against the searched player. Positions at the depth cutoff are scored by alive hands.
+#[derive(Clone)]
+pub struct Minimax {
+    /// Number of plies searched for each move
+    max_depth: usize,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for Minimax {
+    fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+        let i = match state.get_status() {
+            state::status::Status::Turn { i } => i,
+            state::status::Status::Over { i: _ } => panic!("game is over"),
+        };
+        let mut state = state.clone();
+        let actions: Vec<_> = state.iter_actions().collect();
+        let mut best = None;
+        let mut alpha = -WIN;
+        for action in actions {
+            state.play_action(&action).expect("valid action");
+            let score = self.search(&mut state, i, 1, alpha, WIN);
+            state.undo_action(&action).expect("valid action");
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(action);
+            }
+        }
+        best.expect("multiple actions")
+    }
+}
+
+impl Minimax {
+    pub fn new(max_depth: usize) -> Minimax {
+        Minimax { max_depth }
+    }
+
+    /// Score of `state` for player `i` bounded by `alpha` and `beta`
+    fn search<const N: usize, T: state_space::StateSpace<N>>(
+        &self,
+        state: &mut state::State<N, T>,
+        i: usize,
+        depth: usize,
+        mut alpha: i32,
+        mut beta: i32,
+    ) -> i32 {
+        let j = match state.get_status() {
+            state::status::Status::Over { i: last } if (last == i) != T::MISERE => {
+                return WIN - depth as i32
+            }
+            state::status::Status::Over { i: _ } => return depth as i32 - WIN,
+            state::status::Status::Turn { i: j } => j,
+        };
+        if depth >= self.max_depth {
+            return evaluate(state, i);
+        }
+        let actions: Vec<_> = state.iter_actions().collect();
+        let mut value = if i == j { -WIN } else { WIN };
+        for action in actions {
+            state.play_action(&action).expect("valid action");
+            let score = self.search(state, i, depth + 1, alpha, beta);
+            state.undo_action(&action).expect("valid action");
+            if i == j {
+                value = value.max(score);
+                alpha = alpha.max(value);
+            } else {
+                value = value.min(score);
+                beta = beta.min(value);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        value
+    }
+}
+
+/// Alive hands of player `i` less alive hands of every opponent
+pub(super) fn evaluate<const N: usize, T: state_space::StateSpace<N>>(
+    state: &state::State<N, T>,
+    i: usize,
+) -> i32 {
+    state
+        .get_players()
+        .iter()
+        .enumerate()
+        .map(|(j, player)| {
+            let alive = player.alive_hand_count() as i32;
+            if i == j {
+                alive
+            } else {
+                -alive
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{
+        chopsticks::Chopsticks, misere_chopsticks::MisereChopsticks, StateSpace,
+    };
+
+    /// The `short_game` position one attack before player 0 wins
+    fn short_game_state() -> state::State<2, Chopsticks> {
+        let mut state = Chopsticks.get_initial_state(); // 1111
+        assert!(state.play_attack(0, 1, 0, 1).is_ok()); // 1112
+        assert!(state.play_attack(1, 0, 1, 1).is_ok()); // 1312
+        assert!(state.play_attack(0, 1, 1, 1).is_ok()); // 1310
+        assert!(state.play_attack(1, 0, 0, 1).is_ok()); // 1410
+        state
+    }
+
+    #[test]
+    fn short_game_win() {
+        let state = short_game_state();
+        let winning_action = state::action::Action::Attack {
+            i: 0,
+            j: 1,
+            a: 1,
+            b: 0,
+        };
+        for max_depth in 1..=4 {
+            assert_eq!(Minimax::new(max_depth).get_action(&state), winning_action);
+        }
+    }
+
+    #[test]
+    fn misere_avoids_win() {
+        let state = state::State::<2, MisereChopsticks>::from_abbreviation("1410").expect("valid");
+        let winning_action = state::action::Action::Attack {
+            i: 0,
+            j: 1,
+            a: 1,
+            b: 0,
+        };
+        for max_depth in 1..=4 {
+            assert_ne!(Minimax::new(max_depth).get_action(&state), winning_action);
+        }
+    }
+
+    #[test]
+    fn initial_state_action() {
+        let state = Chopsticks.get_initial_state();
+        let action = Minimax::new(4).get_action(&state);
+        assert!(state.iter_actions().any(|legal| legal == action));
+    }
+}
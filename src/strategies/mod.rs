@@ -1,8 +1,17 @@
 use crate::{state, state_space};
 
 pub mod command_prompt;
+pub mod epsilon_greedy;
+pub mod heuristic;
+pub mod mcts;
+pub mod minimax;
+pub mod negamax;
+pub mod perfect;
 pub mod pure_monte_carlo;
+pub mod q_learning;
 pub mod random;
+pub mod scripted;
+pub mod voting;
 
 /// 'get_action provider' or an individual player
 pub trait Strategy<const N: usize, T: state_space::StateSpace<N>> {
@@ -0,0 +1,141 @@
+use super::*;
+use crate::{cache, state, state_space};
+
+/// Score of a won game for the player to move, less the plies it took to win
+const WIN: i32 = 1_000_000;
+
+/// Best action according to a `depth` ply negamax search. Scores are negated between plies so it
+/// is only a `Strategy` for two player zero-sum games. The search plays and undoes actions on a
+/// single `State` rather than cloning it for each node. Evaluations at the depth cutoff are
+/// memoized across searches by serialized state.
+#[derive(Clone)]
+pub struct Negamax {
+    /// Number of plies searched for each move
+    depth: usize,
+
+    /// Evaluations of positions at the depth cutoff
+    table: cache::TranspositionTable<i32>,
+}
+
+impl<T: state_space::StateSpace<2>> Strategy<2, T> for Negamax {
+    fn get_action(&mut self, state: &state::State<2, T>) -> state::action::Action<2, T> {
+        if let state::status::Status::Over { i: _ } = state.get_status() {
+            panic!("game is over");
+        }
+        let mut state = state.clone();
+        let actions: Vec<_> = state.iter_actions().collect();
+        let mut best = None;
+        let mut alpha = -WIN;
+        for action in actions {
+            let score = -self.search_action(&mut state, &action, 1, -WIN, -alpha);
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(action);
+            }
+        }
+        best.expect("multiple actions")
+    }
+}
+
+impl Negamax {
+    pub fn new(depth: usize) -> Negamax {
+        Negamax::with_capacity(depth, cache::DEFAULT_CAPACITY)
+    }
+
+    /// Search with at most `capacity` memoized evaluations
+    pub fn with_capacity(depth: usize, capacity: usize) -> Negamax {
+        Negamax {
+            depth,
+            table: cache::TranspositionTable::new(capacity),
+        }
+    }
+
+    pub fn get_table(&self) -> &cache::TranspositionTable<i32> {
+        &self.table
+    }
+
+    /// Score of `state` for the player to move bounded by `alpha` and `beta`
+    fn search<T: state_space::StateSpace<2>>(
+        &mut self,
+        state: &mut state::State<2, T>,
+        ply: usize,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        if let state::status::Status::Over { i: _ } = state.get_status() {
+            // The previous player made the final move
+            return if T::MISERE {
+                WIN - ply as i32
+            } else {
+                ply as i32 - WIN
+            };
+        }
+        if ply >= self.depth {
+            let serial = T::serialize_state(state);
+            return self
+                .table
+                .get_or_insert_with(serial, || minimax::evaluate(state, state.get_i()));
+        }
+        let actions: Vec<_> = state.iter_actions().collect();
+        let mut value = -WIN;
+        for action in actions {
+            let score = -self.search_action(state, &action, ply + 1, -beta, -alpha);
+            value = value.max(score);
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+        value
+    }
+
+    /// Score of `action` for the player to move after which `state` is restored
+    fn search_action<T: state_space::StateSpace<2>>(
+        &mut self,
+        state: &mut state::State<2, T>,
+        action: &state::action::Action<2, T>,
+        ply: usize,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        #[cfg(debug_assertions)]
+        let before = (state.get_i(), state.get_abbreviation());
+        state.play_action(action).expect("valid action");
+        let score = self.search(state, ply, alpha, beta);
+        state.undo_action(action).expect("valid action");
+        #[cfg(debug_assertions)]
+        assert_eq!((state.get_i(), state.get_abbreviation()), before);
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{multi_strategy::MultiStrategy, Game};
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn never_loses_to_random() {
+        for g in 0..10 {
+            let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+                Box::new(Negamax::new(6)),
+                Box::new(random::Random::seeded(g)),
+            ];
+            let mut game = MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+            let ranks = game.get_rankings();
+            assert_ne!(ranks[1], 1);
+        }
+    }
+
+    #[test]
+    fn transposition_hits() {
+        // Attacks against either hand from the initial state reach the same positions
+        let mut negamax = Negamax::new(4);
+        let state = Chopsticks.get_initial_state();
+        let action = negamax.get_action(&state);
+        assert!(state.iter_actions().any(|legal| legal == action));
+        assert!(negamax.get_table().get_hits() > 0);
+        assert!(!negamax.get_table().is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use super::*;
+use crate::solver::{self, Outcome};
+use crate::{state, state_space};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Action preserving the best outcome of a `solver::solve` table, preferring a win, then a draw,
+/// then a loss. Wins are as quick and losses as slow as possible and remaining ties go to the
+/// first action. Positions missing from the table are treated as draws.
+#[derive(Clone)]
+pub struct Perfect {
+    /// Outcome of each `State` for its current player and plies until the game is over keyed by
+    /// `serialize_state`
+    outcomes: HashMap<u32, (Outcome, u32)>,
+}
+
+impl<T: state_space::StateSpace<2>> Strategy<2, T> for Perfect {
+    fn get_action(&mut self, state: &state::State<2, T>) -> state::action::Action<2, T> {
+        if state.is_terminal() {
+            panic!("game is over");
+        }
+        let mut state = state.clone();
+        let i = state.get_i();
+        let actions: Vec<_> = state.iter_actions().collect();
+        actions
+            .into_iter()
+            .min_by_key(|action| {
+                state.play_action(action).expect("valid action");
+                let (outcome, distance) =
+                    solver::get_solution(&self.outcomes, &state).unwrap_or((Outcome::Draw, 0));
+                let outcome = if state.get_i() == i {
+                    outcome
+                } else {
+                    outcome.flip()
+                };
+                state.undo_action(action).expect("valid action");
+                let delay = match outcome {
+                    Outcome::Win => -(distance as i64),
+                    Outcome::Draw => 0,
+                    Outcome::Loss => distance as i64,
+                };
+                Reverse((get_preference(outcome), delay))
+            })
+            .expect("multiple actions")
+    }
+}
+
+impl Perfect {
+    pub fn new(outcomes: HashMap<u32, (Outcome, u32)>) -> Perfect {
+        Perfect { outcomes }
+    }
+}
+
+/// Higher is better for the player to move
+fn get_preference(outcome: Outcome) -> u8 {
+    match outcome {
+        Outcome::Loss => 0,
+        Outcome::Draw => 1,
+        Outcome::Win => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{multi_strategy::MultiStrategy, Game};
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn never_loses_to_random() {
+        let outcomes = solver::solve(&Chopsticks);
+        for g in 0..20 {
+            let seat = g % 2;
+            let mut strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+                Box::new(random::Random::seeded(g as u64)),
+                Box::new(random::Random::seeded(g as u64)),
+            ];
+            strategies[seat] = Box::new(Perfect::new(outcomes.clone()));
+            let mut game = MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+            let ranks = game.get_rankings();
+            assert!(ranks[seat] <= ranks[1 - seat], "lost game {g}");
+        }
+    }
+
+    #[test]
+    fn takes_win() {
+        let state = state::State::<2, Chopsticks>::from_abbreviation("1410").expect("valid");
+        let winning_action = state::action::Action::Attack {
+            i: 0,
+            j: 1,
+            a: 1,
+            b: 0,
+        };
+        let mut perfect = Perfect::new(solver::solve(&Chopsticks));
+        assert_eq!(perfect.get_action(&state), winning_action);
+    }
+}
@@ -4,12 +4,14 @@ use game::Game;
 use std::marker::PhantomData;
 
 /// Best min sum of rankings move according to `n_sims` for each potential move
-/// this tends not to work very well because its own future moves are random.
-#[derive(Clone)]
+/// this tends not to work very well when its own future moves are random. Ties go to the
+/// least action by its `Ord` so the choice does not depend on the order of `iter_actions`.
 pub struct PureMonteCarlo<const N: usize, T: state_space::StateSpace<N>> {
     /// Number of simulations run for each potential move
     n_sims: usize,
-    strategies: random::Random,
+
+    /// Plays every move of a simulation, `Random` unless set by `with_rollout`
+    rollout: Box<dyn Strategy<N, T>>,
     phantom: PhantomData<T>,
 }
 
@@ -19,21 +21,28 @@ impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for PureMonte
             state::status::Status::Turn { i } => i,
             state::status::Status::Over { i: _ } => panic!("game is over"),
         };
-        state
-            .iter_actions()
-            .min_by_key(|action| {
-                (0..self.n_sims)
+        let mut state = state.clone();
+        let actions: Vec<_> = state.iter_actions().collect();
+        actions
+            .into_iter()
+            .map(|action| {
+                let sum = (0..self.n_sims)
                     .map(|_| {
-                        let mut sim_game = game::single_strategy::SingleStrategy::new(
-                            state.clone(),
-                            &mut self.strategies,
-                        );
-                        sim_game.play_action(action).expect("valid action");
+                        let mut sim_game = Rollout {
+                            state: &mut state,
+                            strategy: self.rollout.as_mut(),
+                            history: Vec::new(),
+                        };
+                        sim_game.play_action(&action).expect("valid action");
                         let ranks = sim_game.get_rankings();
+                        sim_game.undo();
                         ranks[i] as u32
                     })
-                    .sum::<u32>()
+                    .sum::<u32>();
+                (sum, action)
             })
+            .min()
+            .map(|(_, action)| action)
             .expect("non-zero sims")
     }
 }
@@ -42,8 +51,172 @@ impl<const N: usize, T: state_space::StateSpace<N>> PureMonteCarlo<N, T> {
     pub fn new(n_sims: usize) -> PureMonteCarlo<N, T> {
         PureMonteCarlo {
             n_sims,
-            strategies: random::Random {},
+            rollout: Box::new(random::Random::default()),
             phantom: PhantomData {},
         }
     }
+
+    /// Simulates with `Random::seeded` so the chosen actions are reproducible
+    pub fn seeded(n_sims: usize, seed: u64) -> PureMonteCarlo<N, T> {
+        PureMonteCarlo::new(n_sims).with_rollout(Box::new(random::Random::seeded(seed)))
+    }
+
+    /// Simulates with `policy` rather than uniformly random actions
+    pub fn with_rollout(self, policy: Box<dyn Strategy<N, T>>) -> PureMonteCarlo<N, T> {
+        PureMonteCarlo {
+            rollout: policy,
+            ..self
+        }
+    }
+}
+
+/// Simulation on a borrowed `State` that is restored by undoing its history
+struct Rollout<'a, const N: usize, T: state_space::StateSpace<N>> {
+    state: &'a mut state::State<N, T>,
+    strategy: &'a mut dyn Strategy<N, T>,
+    history: Vec<state::action::Action<N, T>>,
+}
+
+impl<'a, const N: usize, T: state_space::StateSpace<N>> Rollout<'a, N, T> {
+    /// Restores the `State` from before the rollout
+    fn undo(&mut self) {
+        while let Some(action) = self.history.pop() {
+            self.state.undo_action(&action).expect("valid action");
+        }
+    }
+}
+
+impl<'a, const N: usize, T: state_space::StateSpace<N>> Game<N, T> for Rollout<'a, N, T> {
+    fn get_action(&mut self) -> Option<state::action::Action<N, T>> {
+        match self.state.get_status() {
+            state::status::Status::Turn { i: _ } => Some(self.strategy.get_action(self.state)),
+            _ => None,
+        }
+    }
+
+    fn play_action(
+        &mut self,
+        action: &state::action::Action<N, T>,
+    ) -> Result<(), state::action::ActionError> {
+        self.state.play_action(action)?;
+        self.history.push(*action);
+        Ok(())
+    }
+
+    fn get_state(&self) -> &state::State<N, T> {
+        self.state
+    }
+
+    fn get_history(&self) -> &[state::action::Action<N, T>] {
+        &self.history
+    }
+
+    fn get_max_repetitions(&self) -> usize {
+        game::MAX_REPETITIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    /// `PureMonteCarlo::get_action` cloning the `State` for every simulation
+    fn get_action_cloning(
+        n_sims: usize,
+        strategies: &mut random::Random,
+        state: &state::State<2, Chopsticks>,
+    ) -> state::action::Action<2, Chopsticks> {
+        let i = state.get_i();
+        state
+            .iter_actions()
+            .map(|action| {
+                let sum = (0..n_sims)
+                    .map(|_| {
+                        let mut sim_game =
+                            game::single_strategy::SingleStrategy::new(state.clone(), strategies);
+                        sim_game.play_action(&action).expect("valid action");
+                        sim_game.get_rankings()[i] as u32
+                    })
+                    .sum::<u32>();
+                (sum, action)
+            })
+            .min()
+            .map(|(_, action)| action)
+            .expect("non-zero sims")
+    }
+
+    #[test]
+    fn same_as_cloning() {
+        for abbreviation in ["1111", "1312", "2323", "1410"] {
+            let state = state::State::from_abbreviation(abbreviation).expect("valid");
+            for seed in 0..5 {
+                let mut strategies = random::Random::seeded(seed);
+                let expected = get_action_cloning(20, &mut strategies, &state);
+                let mut pure_monte_carlo = PureMonteCarlo {
+                    n_sims: 20,
+                    rollout: Box::new(random::Random::seeded(seed)),
+                    phantom: PhantomData {},
+                };
+                assert_eq!(pure_monte_carlo.get_action(&state), expected);
+                // Both consumed the same random numbers
+                let next_action = pure_monte_carlo.rollout.get_action(&state);
+                assert_eq!(next_action, strategies.get_action(&state));
+            }
+        }
+    }
+
+    #[test]
+    fn heuristic_rollout() {
+        let state = state::State::<2, Chopsticks>::with_hands([[1, 3], [2, 2]], 1).expect("valid");
+        let split = state::action::Action::Split {
+            i: 1,
+            hands_0: [2, 2],
+            hands_1: [1, 3],
+        };
+        let mut heavy =
+            PureMonteCarlo::new(1).with_rollout(Box::new(heuristic::Heuristic::default()));
+        assert_eq!(heavy.get_action(&state), split);
+        for seed in 0..5 {
+            let mut uniform =
+                PureMonteCarlo::new(20).with_rollout(Box::new(random::Random::seeded(seed)));
+            assert_ne!(uniform.get_action(&state), split);
+        }
+    }
+
+    #[test]
+    fn tie_break() {
+        // Either hand wins by attacking the last hand so both attacks always tie
+        let state = state::State::<2, Chopsticks>::from_abbreviation("4410").expect("valid");
+        let winning: Vec<_> = state.iter_winning_actions().collect();
+        assert_eq!(winning.len(), 2);
+        for seed in 0..2 {
+            let mut pure_monte_carlo =
+                PureMonteCarlo::new(5).with_rollout(Box::new(random::Random::seeded(seed)));
+            let action = pure_monte_carlo.get_action(&state);
+            assert_eq!(Some(&action), winning.iter().min());
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        for abbreviation in ["1111", "1312", "2323"] {
+            let state =
+                state::State::<2, Chopsticks>::from_abbreviation(abbreviation).expect("valid");
+            for seed in 0..3 {
+                let mut a = PureMonteCarlo::seeded(10, seed);
+                let mut b = PureMonteCarlo::seeded(10, seed);
+                for _ in 0..3 {
+                    assert_eq!(a.get_action(&state), b.get_action(&state));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn initial_state_action() {
+        let state = Chopsticks.get_initial_state();
+        let action = PureMonteCarlo::new(10).get_action(&state);
+        assert!(state.iter_actions().any(|legal| legal == action));
+    }
 }
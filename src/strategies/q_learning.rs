@@ -0,0 +1,284 @@
+use super::*;
+use crate::game::{self, Game};
+use crate::{state, state_space};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+
+/// Reward for the player who played the action from the first `State` to reach the second,
+/// for shaping rewards between the terminal rewards of training
+pub type RewardFn<const N: usize, T> =
+    fn(&state::State<N, T>, &state::action::Action<N, T>, &state::State<N, T>) -> f64;
+
+/// Default `RewardFn` which is 1 for a win, -1 for a loss, and 0 until the game is over or when
+/// `State::get_winner` is unknown
+pub fn terminal_reward<const N: usize, T: state_space::StateSpace<N>>(
+    _prev_state: &state::State<N, T>,
+    action: &state::action::Action<N, T>,
+    next_state: &state::State<N, T>,
+) -> f64 {
+    match next_state.get_winner() {
+        Some(i) if i == action.get_i() => 1.0,
+        Some(_) => -1.0,
+        None => 0.0,
+    }
+}
+
+/// Greedy action according to a table of action values learned by tabular Q-learning. Values
+/// are keyed by serialized state and serialized action and unseen pairs are valued at 0.
+#[derive(Clone)]
+pub struct QLearning {
+    /// Estimated value of `(state serial, action serial)` for the player to move
+    pub table: HashMap<(u32, u32), f64>,
+
+    /// Step size in `0.0..=1.0` of each update
+    pub learning_rate: f64,
+
+    /// Weight in `0.0..=1.0` of the value of the next state
+    pub discount: f64,
+
+    /// Probability in `0.0..=1.0` of a random action while training
+    pub exploration: f64,
+
+    rng: StdRng,
+}
+
+impl Default for QLearning {
+    fn default() -> QLearning {
+        QLearning::new(0.5, 0.9, 0.2, None)
+    }
+}
+
+impl QLearning {
+    /// Explores from entropy or reproducibly from `seed`
+    pub fn new(
+        learning_rate: f64,
+        discount: f64,
+        exploration: f64,
+        seed: Option<u64>,
+    ) -> QLearning {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        QLearning {
+            table: HashMap::new(),
+            learning_rate,
+            discount,
+            exploration,
+            rng,
+        }
+    }
+
+    /// Learned value of playing `action` from `state`
+    pub fn get_value<const N: usize, T: state_space::StateSpace<N>>(
+        &self,
+        state: &state::State<N, T>,
+        action: &state::action::Action<N, T>,
+    ) -> f64 {
+        let key = (T::serialize_state(state), T::serialize_action(action));
+        self.table.get(&key).copied().unwrap_or(0.0)
+    }
+
+    /// Moves the value of `action` from `prev_state` towards `reward` plus the discounted value
+    /// of the best action from `next_state`. Terminal next states are worth nothing more.
+    pub fn observe<const N: usize, T: state_space::StateSpace<N>>(
+        &mut self,
+        prev_state: &state::State<N, T>,
+        action: &state::action::Action<N, T>,
+        reward: f64,
+        next_state: &state::State<N, T>,
+    ) {
+        let future = if next_state.is_terminal() {
+            0.0
+        } else {
+            next_state
+                .iter_actions()
+                .map(|next_action| self.get_value(next_state, &next_action))
+                .fold(f64::NEG_INFINITY, f64::max)
+        };
+        let target = reward + self.discount * future;
+        let key = (T::serialize_state(prev_state), T::serialize_action(action));
+        let value = self.table.entry(key).or_insert(0.0);
+        *value += self.learning_rate * (target - *value);
+    }
+
+    /// Plays `episodes` games against `opponent`, rotating the seat of the learner. The
+    /// learner is rewarded by its rank at the end of each game from 1 for first to -1 for last.
+    pub fn train<const N: usize, T: state_space::StateSpace<N> + std::fmt::Debug>(
+        &mut self,
+        episodes: usize,
+        opponent: &mut dyn Strategy<N, T>,
+    ) {
+        self.train_with_reward(episodes, opponent, |_, _, _| 0.0);
+    }
+
+    /// `train` where each of the learner's actions is also rewarded by `reward`
+    pub fn train_with_reward<const N: usize, T: state_space::StateSpace<N> + std::fmt::Debug>(
+        &mut self,
+        episodes: usize,
+        opponent: &mut dyn Strategy<N, T>,
+        reward: RewardFn<N, T>,
+    ) {
+        for episode in 0..episodes {
+            let seat = episode % N;
+            let mut game = Episode {
+                learner: self,
+                opponent: &mut *opponent,
+                seat,
+                reward,
+                state: state::State::default(),
+                history: Vec::new(),
+                transitions: Vec::new(),
+            };
+            let ranks = game.get_rankings();
+            let Episode {
+                state, transitions, ..
+            } = game;
+            let rank_reward = if ranks.iter().all(|&rank| rank == ranks[seat]) {
+                0.0
+            } else {
+                (N as f64 + 1.0 - 2.0 * ranks[seat] as f64) / (N as f64 - 1.0)
+            };
+            // Updating from the end of the game propagates the reward in a single episode
+            let n_transitions = transitions.len();
+            for k in (0..n_transitions).rev() {
+                let (prev_state, action, reward) = &transitions[k];
+                if k + 1 == n_transitions {
+                    self.observe(prev_state, action, reward + rank_reward, &state);
+                } else {
+                    self.observe(prev_state, action, *reward, &transitions[k + 1].0);
+                }
+            }
+        }
+    }
+
+    /// Random action with probability `exploration` and otherwise the greedy action
+    fn explore<const N: usize, T: state_space::StateSpace<N>>(
+        &mut self,
+        state: &state::State<N, T>,
+    ) -> state::action::Action<N, T> {
+        if self.rng.gen::<f64>() < self.exploration {
+            let n = self.rng.gen_range(0..state.count_actions());
+            state.nth_action(n).expect("multiple actions")
+        } else {
+            self.get_action(state)
+        }
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for QLearning {
+    fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+        let mut best = None;
+        let mut best_value = f64::NEG_INFINITY;
+        for action in state.iter_actions() {
+            let value = self.get_value(state, &action);
+            if best.is_none() || value > best_value {
+                best_value = value;
+                best = Some(action);
+            }
+        }
+        best.expect("multiple actions")
+    }
+}
+
+/// A training game that records the learner's states and actions
+struct Episode<'a, const N: usize, T: state_space::StateSpace<N>> {
+    learner: &'a mut QLearning,
+    opponent: &'a mut dyn Strategy<N, T>,
+    seat: usize,
+    reward: RewardFn<N, T>,
+    state: state::State<N, T>,
+    history: Vec<state::action::Action<N, T>>,
+
+    /// The learner's states, actions, and shaped rewards
+    transitions: Vec<(state::State<N, T>, state::action::Action<N, T>, f64)>,
+}
+
+impl<'a, const N: usize, T: state_space::StateSpace<N>> Game<N, T> for Episode<'a, N, T> {
+    fn get_action(&mut self) -> Option<state::action::Action<N, T>> {
+        match self.state.get_status() {
+            state::status::Status::Turn { i } if i == self.seat => {
+                let action = self.learner.explore(&self.state);
+                self.transitions.push((self.state.clone(), action, 0.0));
+                Some(action)
+            }
+            state::status::Status::Turn { i: _ } => Some(self.opponent.get_action(&self.state)),
+            _ => None,
+        }
+    }
+
+    fn play_action(
+        &mut self,
+        action: &state::action::Action<N, T>,
+    ) -> Result<(), state::action::ActionError> {
+        let i = self.state.get_i();
+        self.state.play_action(action)?;
+        self.history.push(*action);
+        if i == self.seat {
+            let (prev_state, _, reward) = self.transitions.last_mut().expect("learner action");
+            *reward = (self.reward)(prev_state, action, &self.state);
+        }
+        Ok(())
+    }
+
+    fn get_state(&self) -> &state::State<N, T> {
+        &self.state
+    }
+
+    fn get_history(&self) -> &[state::action::Action<N, T>] {
+        &self.history
+    }
+
+    fn get_max_repetitions(&self) -> usize {
+        game::MAX_REPETITIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::multi_strategy::MultiStrategy;
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    #[test]
+    fn terminal_reward_only_at_end() {
+        let mut state = Chopsticks.get_initial_state();
+        let mut rewards = Vec::new();
+        for (i, j, a, b) in [
+            (0, 1, 0, 1),
+            (1, 0, 1, 1),
+            (0, 1, 1, 1),
+            (1, 0, 0, 1),
+            (0, 1, 1, 0),
+        ] {
+            let prev_state = state.clone();
+            let action = state::action::Action::Attack { i, j, a, b };
+            assert!(state.play_action(&action).is_ok());
+            let reward: RewardFn<2, Chopsticks> = terminal_reward;
+            rewards.push(reward(&prev_state, &action, &state));
+        }
+        assert_eq!(rewards, [0.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn beats_random() {
+        let mut q_learning = QLearning::new(0.5, 0.9, 0.2, Some(0));
+        q_learning.train::<2, Chopsticks>(2000, &mut random::Random::seeded(1));
+        assert!(!q_learning.table.is_empty());
+        let n_games = 100;
+        let mut wins = 0;
+        for g in 0..n_games {
+            let seat = g % 2;
+            let mut strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+                Box::new(random::Random::seeded(g as u64)),
+                Box::new(random::Random::seeded(g as u64)),
+            ];
+            strategies[seat] = Box::new(q_learning.clone());
+            let mut game = MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+            if game.get_rankings()[seat] == 1 {
+                wins += 1;
+            }
+        }
+        assert!(wins > n_games / 2, "won {wins} of {n_games}");
+    }
+}
@@ -1,15 +1,32 @@
 use crate::{state, state_space};
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 /// Random action of all potential next actions
-#[derive(Clone, Default)]
-pub struct Random;
+#[derive(Clone)]
+pub struct Random {
+    rng: StdRng,
+}
+
+impl Default for Random {
+    fn default() -> Random {
+        Random {
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl Random {
+    /// Reproducible sequence of random actions
+    pub fn seeded(seed: u64) -> Random {
+        Random {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
 
 impl<const N: usize, T: state_space::StateSpace<N>> super::Strategy<N, T> for Random {
     fn get_action(&mut self, gamestate: &state::State<N, T>) -> state::action::Action<N, T> {
-        let mut actions: Vec<_> = gamestate.iter_actions().collect();
-        *actions
-            .choose_mut(&mut rand::thread_rng())
-            .expect("multiple actions")
+        let n = self.rng.gen_range(0..gamestate.count_actions());
+        gamestate.nth_action(n).expect("multiple actions")
     }
 }
@@ -0,0 +1,74 @@
+use super::*;
+use crate::{state, state_space};
+use std::collections::VecDeque;
+
+/// Each queued action in order regardless of the state
+pub struct Scripted<const N: usize, T: state_space::StateSpace<N>> {
+    actions: VecDeque<state::action::Action<N, T>>,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Scripted<N, T> {
+    pub fn new(actions: Vec<state::action::Action<N, T>>) -> Scripted<N, T> {
+        Scripted {
+            actions: actions.into(),
+        }
+    }
+
+    /// Number of actions left in the script
+    pub fn get_remaining(&self) -> usize {
+        self.actions.len()
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for Scripted<N, T> {
+    /// Panics once the script runs out of actions
+    fn get_action(&mut self, _state: &state::State<N, T>) -> state::action::Action<N, T> {
+        self.actions.pop_front().expect("script has actions")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{multi_strategy::MultiStrategy, Game};
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    /// The attacks of `state::tests::short_game` by player `i`
+    fn short_game_script(i: usize) -> Scripted<2, Chopsticks> {
+        let attacks = [
+            (0, 1, 0, 1),
+            (1, 0, 1, 1),
+            (0, 1, 1, 1),
+            (1, 0, 0, 1),
+            (0, 1, 1, 0),
+        ];
+        Scripted::new(
+            attacks
+                .into_iter()
+                .filter(|&(attacker, _, _, _)| attacker == i)
+                .map(|(i, j, a, b)| state::action::Action::Attack { i, j, a, b })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn short_game() {
+        let strategies: [Box<dyn Strategy<2, Chopsticks>>; 2] = [
+            Box::new(short_game_script(0)),
+            Box::new(short_game_script(1)),
+        ];
+        let mut game = MultiStrategy::new(Chopsticks.get_initial_state(), strategies);
+        assert_eq!(game.get_rankings(), [1, 2]);
+        assert_eq!(game.history.len(), 5);
+        assert_eq!(game.state.get_abbreviation(), "1400");
+    }
+
+    #[test]
+    fn remaining() {
+        let mut scripted = short_game_script(1);
+        let state = Chopsticks.get_initial_state();
+        assert_eq!(scripted.get_remaining(), 2);
+        scripted.get_action(&state);
+        assert_eq!(scripted.get_remaining(), 1);
+    }
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::{state, state_space};
+
+/// The action proposed by the most total weight of its `inner` strategies. Ties go to the
+/// action proposed first so proposals that don't overlap fall back to the heaviest proposer.
+pub struct Voting<const N: usize, T: state_space::StateSpace<N>> {
+    /// Each strategy with the weight of its vote
+    pub inner: Vec<(Box<dyn Strategy<N, T>>, f64)>,
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Voting<N, T> {
+    pub fn new(inner: Vec<(Box<dyn Strategy<N, T>>, f64)>) -> Voting<N, T> {
+        Voting { inner }
+    }
+}
+
+impl<const N: usize, T: state_space::StateSpace<N>> Strategy<N, T> for Voting<N, T> {
+    /// Panics without any inner strategies
+    fn get_action(&mut self, state: &state::State<N, T>) -> state::action::Action<N, T> {
+        let mut tallies: Vec<(state::action::Action<N, T>, f64)> = Vec::new();
+        for (strategy, weight) in self.inner.iter_mut() {
+            let action = strategy.get_action(state);
+            match tallies.iter_mut().find(|(proposal, _)| *proposal == action) {
+                Some((_, total)) => *total += *weight,
+                None => tallies.push((action, *weight)),
+            }
+        }
+        tallies
+            .into_iter()
+            .reduce(|best, tally| if tally.1 > best.1 { tally } else { best })
+            .map(|(action, _)| action)
+            .expect("inner strategies")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+
+    /// A strategy that proposes `action` once
+    fn propose(action: state::action::Action<2, Chopsticks>) -> Box<dyn Strategy<2, Chopsticks>> {
+        Box::new(scripted::Scripted::new(vec![action]))
+    }
+
+    #[test]
+    fn weighted_votes() {
+        let state = Chopsticks.get_initial_state();
+        let a = state::action::Action::Attack {
+            i: 0,
+            j: 1,
+            a: 0,
+            b: 0,
+        };
+        let b = state::action::Action::Attack {
+            i: 0,
+            j: 1,
+            a: 0,
+            b: 1,
+        };
+
+        let mut majority = Voting::new(vec![
+            (propose(b), 1.0),
+            (propose(a), 1.0),
+            (propose(a), 1.0),
+        ]);
+        assert_eq!(majority.get_action(&state), a);
+
+        let mut heaviest = Voting::new(vec![(propose(b), 1.0), (propose(a), 3.0)]);
+        assert_eq!(heaviest.get_action(&state), a);
+
+        let mut tied = Voting::new(vec![(propose(b), 2.0), (propose(a), 2.0)]);
+        assert_eq!(tied.get_action(&state), b);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::game::{self, Game};
+use crate::strategies::Strategy;
+use crate::{state, state_space};
+
+/// Tallies of a round robin where index `k` is the `k`th strategy
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TournamentResults {
+    pub wins: Vec<usize>,
+    pub losses: Vec<usize>,
+    pub draws: Vec<usize>,
+
+    /// `pair_wins[a][b]` is the number of games strategy `a` won against strategy `b`
+    pub pair_wins: Vec<Vec<usize>>,
+}
+
+impl TournamentResults {
+    fn new(n_strategies: usize) -> TournamentResults {
+        TournamentResults {
+            wins: vec![0; n_strategies],
+            losses: vec![0; n_strategies],
+            draws: vec![0; n_strategies],
+            pair_wins: vec![vec![0; n_strategies]; n_strategies],
+        }
+    }
+
+    /// Number of games strategy `k` played
+    pub fn get_games(&self, k: usize) -> usize {
+        self.wins[k] + self.losses[k] + self.draws[k]
+    }
+
+    /// Fraction of its games strategy `k` won or 0 if it played none
+    pub fn get_win_rate(&self, k: usize) -> f64 {
+        match self.get_games(k) {
+            0 => 0.0,
+            games => self.wins[k] as f64 / games as f64,
+        }
+    }
+}
+
+/// Plays `games_per_pair` games between every pair of `strategies` from the initial state of
+/// `space`, alternating who goes first. Games are ranked by `get_rankings` so repetitions draw.
+pub fn run_round_robin<T: state_space::StateSpace<2> + std::fmt::Debug>(
+    space: &T,
+    mut strategies: Vec<Box<dyn Strategy<2, T>>>,
+    games_per_pair: usize,
+) -> TournamentResults {
+    let n_strategies = strategies.len();
+    let mut results = TournamentResults::new(n_strategies);
+    for b in 0..n_strategies {
+        for a in 0..b {
+            let (left, right) = strategies.split_at_mut(b);
+            let (strategy_a, strategy_b) = (left[a].as_mut(), right[0].as_mut());
+            for g in 0..games_per_pair {
+                let initial = space.get_initial_state();
+                match play_seated(initial, [&mut *strategy_a, &mut *strategy_b], g) {
+                    Some(0) => {
+                        results.wins[a] += 1;
+                        results.losses[b] += 1;
+                        results.pair_wins[a][b] += 1;
+                    }
+                    Some(_) => {
+                        results.wins[b] += 1;
+                        results.losses[a] += 1;
+                        results.pair_wins[b][a] += 1;
+                    }
+                    None => {
+                        results.draws[a] += 1;
+                        results.draws[b] += 1;
+                    }
+                }
+            }
+        }
+    }
+    results
+}
+
+/// Index of the winning strategy of game `g` from `initial` or `None` for a draw. The second
+/// strategy goes first in odd games and games are ranked by `get_rankings`.
+pub(crate) fn play_seated<T: state_space::StateSpace<2>>(
+    initial: state::State<2, T>,
+    strategies: [&mut dyn Strategy<2, T>; 2],
+    g: usize,
+) -> Option<usize> {
+    let [strategy_0, strategy_1] = strategies;
+    let ranks = if g.is_multiple_of(2) {
+        Match::new(initial, [strategy_0, strategy_1]).get_rankings()
+    } else {
+        let [rank_1, rank_0] = Match::new(initial, [strategy_1, strategy_0]).get_rankings();
+        [rank_0, rank_1]
+    };
+    match ranks {
+        [1, 2] => Some(0),
+        [2, 1] => Some(1),
+        _ => None,
+    }
+}
+
+/// A game between two borrowed strategies
+struct Match<'a, T: state_space::StateSpace<2>> {
+    strategies: [&'a mut dyn Strategy<2, T>; 2],
+    state: state::State<2, T>,
+    history: Vec<state::action::Action<2, T>>,
+}
+
+impl<'a, T: state_space::StateSpace<2>> Match<'a, T> {
+    fn new(state: state::State<2, T>, strategies: [&'a mut dyn Strategy<2, T>; 2]) -> Match<'a, T> {
+        Match {
+            strategies,
+            state,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T: state_space::StateSpace<2>> Game<2, T> for Match<'a, T> {
+    fn get_action(&mut self) -> Option<state::action::Action<2, T>> {
+        match self.state.get_status() {
+            state::status::Status::Turn { i } => Some(self.strategies[i].get_action(&self.state)),
+            _ => None,
+        }
+    }
+
+    fn play_action(
+        &mut self,
+        action: &state::action::Action<2, T>,
+    ) -> Result<(), state::action::ActionError> {
+        self.state.play_action(action)?;
+        self.history.push(*action);
+        Ok(())
+    }
+
+    fn get_state(&self) -> &state::State<2, T> {
+        &self.state
+    }
+
+    fn get_history(&self) -> &[state::action::Action<2, T>] {
+        &self.history
+    }
+
+    fn get_max_repetitions(&self) -> usize {
+        game::MAX_REPETITIONS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_space::chopsticks::Chopsticks;
+    use crate::strategies::{negamax, random};
+
+    #[test]
+    fn round_robin() {
+        let strategies: Vec<Box<dyn Strategy<2, Chopsticks>>> = vec![
+            Box::new(negamax::Negamax::new(6)),
+            Box::new(random::Random::seeded(0)),
+        ];
+        let results = run_round_robin(&Chopsticks, strategies, 4);
+        assert_eq!(results.get_games(0), 4);
+        assert_eq!(results.get_games(1), 4);
+        assert_eq!(results.wins[0], results.losses[1]);
+        assert_eq!(results.wins[1], 0);
+        assert_eq!(results.pair_wins[0][1], results.wins[0]);
+        assert!(results.get_win_rate(0) >= results.get_win_rate(1));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::state;
+use crate::state_space::{chopsticks::Chopsticks, StateSpace};
+use wasm_bindgen::prelude::*;
+
+/// A game of `Chopsticks` for JavaScript where actions are exchanged by `serialize_action`
+#[wasm_bindgen]
+pub struct WasmGame {
+    state: state::State<2, Chopsticks>,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGame {
+        WasmGame {
+            state: Chopsticks.get_initial_state(),
+        }
+    }
+
+    /// Serials of every legal action for the current player
+    pub fn legal_actions(&self) -> JsValue {
+        JsValue::from(self.legal_action_serials())
+    }
+
+    /// Plays the action with `serial` for the current player
+    pub fn play(&mut self, serial: u32) -> Result<(), JsValue> {
+        self.play_serial(serial)
+            .map_err(|error| JsValue::from_str(&error))
+    }
+
+    pub fn abbreviation(&self) -> String {
+        self.state.get_abbreviation()
+    }
+}
+
+impl Default for WasmGame {
+    fn default() -> WasmGame {
+        WasmGame::new()
+    }
+}
+
+impl WasmGame {
+    fn legal_action_serials(&self) -> Vec<u32> {
+        if self.state.is_terminal() {
+            return Vec::new();
+        }
+        self.state
+            .iter_actions()
+            .map(|action| Chopsticks::serialize_action(&action))
+            .collect()
+    }
+
+    fn play_serial(&mut self, serial: u32) -> Result<(), String> {
+        let action = Chopsticks::deserialize_action(&self.state, serial)
+            .map_err(|error| format!("{error:?}"))?;
+        self.state
+            .play_action(&action)
+            .map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_serials() {
+        let mut game = WasmGame::new();
+        assert_eq!(game.abbreviation(), "1111");
+        let serials = game.legal_action_serials();
+        assert_eq!(serials.len(), 4);
+        assert!(game.play_serial(serials[0]).is_ok());
+        assert_eq!(game.abbreviation(), "1121");
+        assert!(game.play_serial(Chopsticks::ACTION_SERIAL_BASE).is_err());
+        assert!(game.play_serial(Chopsticks::ATTACK_SERIAL_BASE).is_err());
+    }
+}